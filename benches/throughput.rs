@@ -0,0 +1,42 @@
+//! Compares the throughput of the CRC engines on a large buffer.
+//! Run with `cargo bench`.
+
+extern crate crc_rocksoft;
+
+use std::hint::black_box;
+use std::time::Instant;
+use crc_rocksoft::CrcHasher;
+use crc_rocksoft::primitive::*;
+
+const SIZE: usize = 64 << 20;
+
+fn measure<H: CrcHasher<u32>>(name: &str, mut hasher: H, data: &[u8]) {
+    let start = Instant::now();
+    hasher.update_from_slice(black_box(data));
+    let checksum = black_box(hasher.finish());
+    let elapsed = start.elapsed();
+    let mib_per_s = (data.len() >> 20) as f64 / elapsed.as_secs_f64();
+    println!("{:<12} {:>10.1} MiB/s (checksum {:08X})", name, mib_per_s, checksum);
+}
+
+fn main() {
+    let data: Vec<u8> = (0..SIZE).map(|i| (i * 7 + (i >> 8)) as u8).collect();
+    // With `--features hw`, the raw loop and the bytewise hasher use carry-less multiplication.
+    let spec = || CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+
+    let raw = spec();
+    let start = Instant::now();
+    let checksum = black_box(raw.finish(raw.checksum_with_state(raw.start(), black_box(&data))));
+    let mib_per_s = (data.len() >> 20) as f64 / start.elapsed().as_secs_f64();
+    println!("{:<12} {:>10.1} MiB/s (checksum {:08X})", "raw loop", mib_per_s, checksum);
+
+    measure("nibble", CrcNibbleTableHasher::from(CrcNibbleTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32)), &data);
+    measure("bytewise", CrcTableHasher::from(spec()), &data);
+    measure("slice-by-4", CrcSlicedTableHasher::from(CrcSlice4Table::from(spec())), &data);
+    measure("slice-by-8", CrcSlicedTableHasher::from(CrcSlice8Table::from(spec())), &data);
+    measure("slice-by-16", CrcSlice16Hasher::from(CrcSlice16Table::new_boxed(spec())), &data);
+
+    // A different algorithm, which is computed by the CPU with `--features hw`.
+    let crc32c = CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    measure("crc32c", CrcTableHasher::from(crc32c), &data);
+}
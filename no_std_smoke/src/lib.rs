@@ -0,0 +1,18 @@
+//! Checks that `crc-rocksoft` builds without the standard library.
+//! Build it on its own (`cargo build -p no_std_smoke`), preferably for a target
+//! that has no `std` at all, e. g. `thumbv7m-none-eabi`.
+
+#![no_std]
+
+extern crate crc_rocksoft;
+
+use crc_rocksoft::CrcHasher;
+use crc_rocksoft::primitive::{CrcTable, CrcTableHasher};
+
+/// Computes the CRC-32/ISO-HDLC checksum of `data`.
+pub fn crc32(data: &[u8]) -> u32 {
+    let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    let mut hasher = CrcTableHasher::from(&spec);
+    hasher.update_from_slice(data);
+    hasher.finish()
+}
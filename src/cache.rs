@@ -0,0 +1,207 @@
+//! A process-wide cache of lookup tables, so that code constructing hashers for the same
+//! algorithms over and over, e. g. on every request of a service, computes each table once.
+//! Only available with the `std` feature.
+//!
+//! [`table_for`](fn.table_for.html) returns an `Arc` shared by everyone who asks for the same
+//! parameters, and `CrcTableHasher` can be constructed from the `Arc` directly:
+//!
+//! ```
+//! use crc_rocksoft::*;
+//! use crc_rocksoft::primitive::*;
+//!
+//! let params = CrcParams { width: 32, poly: 0x04C11DB7u32, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF };
+//! let mut hasher = CrcTableHasher::from(cache::table_for(params));
+//! hasher.update_from_slice(b"123456789");
+//! assert_eq!(hasher.finish(), 0xCBF43926);
+//!
+//! // The table is computed only once.
+//! assert!(std::ptr::eq(hasher.spec(), &*cache::table_for(params)));
+//! ```
+//!
+//! The cache never evicts anything by itself; long-running processes that go through
+//! many algorithms can [`clear`](fn.clear.html) it.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::convert::TryFrom;
+use primitive::{ValueType, CrcParams, CrcTable, CrcError};
+
+
+/// The map from parameters to the shared tables, one per value type.
+pub type TableCache<T> = Mutex<HashMap<CrcParams<T>, Arc<CrcTable<T>>>>;
+
+/// A `ValueType` with its own cache of tables.
+///
+/// It's implemented for all the primitive unsigned integers. A newtype can implement it
+/// with a `static` of its own:
+///
+/// ```
+/// # #[macro_use] extern crate crc_rocksoft;
+/// use std::sync::OnceLock;
+/// use crc_rocksoft::cache::{Cached, TableCache};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// struct Crc16(u16);
+/// newtype_value_type!(Crc16(u16));
+///
+/// impl Cached for Crc16 {
+///     fn cache() -> &'static TableCache<Self> {
+///         static CACHE: OnceLock<TableCache<Crc16>> = OnceLock::new();
+///         CACHE.get_or_init(Default::default)
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub trait Cached: ValueType + Hash + Send + Sync + 'static {
+    /// Returns the cache of the tables for this type.
+    fn cache() -> &'static TableCache<Self>;
+}
+
+macro_rules! impl_cached {
+    ($($t:ty),*) => {
+        $(
+            impl Cached for $t {
+                fn cache() -> &'static TableCache<Self> {
+                    static CACHE: OnceLock<TableCache<$t>> = OnceLock::new();
+                    CACHE.get_or_init(Default::default)
+                }
+            }
+        )*
+
+        /// Empties the caches of all the primitive types, so that their tables are freed
+        /// as soon as the last `Arc` handed out for them is dropped. The tables are computed
+        /// anew when they're asked for again.
+        pub fn clear() {
+            $(clear_for::<$t>();)*
+        }
+    }
+}
+
+impl_cached!(u8, u16, u32, u64, u128, usize);
+
+/// Locks a cache; none of its users panics while holding the lock, but even if one did,
+/// the map would be left consistent, so poisoning is ignored.
+fn lock<T: Cached>() -> MutexGuard<'static, HashMap<CrcParams<T>, Arc<CrcTable<T>>>> {
+    T::cache().lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the shared table for the parameters, computing it if nobody has asked for it yet.
+///
+/// # Panics
+///
+/// Panics if the parameters are invalid; see [`try_table_for`](fn.try_table_for.html).
+pub fn table_for<T: Cached>(params: CrcParams<T>) -> Arc<CrcTable<T>> {
+    try_table_for(params).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Returns the shared table for the parameters, computing it if nobody has asked for it yet.
+///
+/// The cache stays locked while the table is computed, so concurrent callers asking
+/// for the same parameters wait for it instead of computing it too.
+///
+/// Returns an error if the parameters are invalid; see
+/// [`CrcTable::try_new_with_width`](../primitive/struct.CrcTable.html#method.try_new_with_width).
+/// Invalid parameters are not cached.
+pub fn try_table_for<T: Cached>(params: CrcParams<T>) -> Result<Arc<CrcTable<T>>, CrcError<T>> {
+    let mut cache = lock::<T>();
+    if let Some(table) = cache.get(&params) {
+        return Ok(Arc::clone(table));
+    }
+    let table = Arc::new(CrcTable::try_from(params)?);
+    cache.insert(params, Arc::clone(&table));
+    Ok(table)
+}
+
+/// Empties the cache of one type; see [`clear`](fn.clear.html).
+pub fn clear_for<T: Cached>() {
+    lock::<T>().clear();
+}
+
+/// Returns the number of tables in the cache of one type.
+pub fn len<T: Cached>() -> usize {
+    lock::<T>().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier, Mutex, MutexGuard, PoisonError};
+    use std::thread;
+    use std::vec::Vec;
+    use primitive::{CrcParams, CrcError};
+    use super::{table_for, try_table_for, clear, clear_for, len};
+
+    /// Serializes the tests, since each of them may clear the caches.
+    fn serial() -> MutexGuard<'static, ()> {
+        static LOCK: Mutex<()> = Mutex::new(());
+        LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn crc32() -> CrcParams<u32> {
+        CrcParams { width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF }
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let _serial = serial();
+        let barrier = Arc::new(Barrier::new(16));
+        let tables: Vec<_> = (0..16).map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                table_for(crc32())
+            })
+        }).collect::<Vec<_>>().into_iter().map(|thread| thread.join().unwrap()).collect();
+        for table in &tables {
+            assert!(Arc::ptr_eq(table, &tables[0]));
+        }
+        assert!(Arc::ptr_eq(&table_for(crc32()), &tables[0]));
+        assert_eq!(tables[0].check(), 0xCBF43926);
+    }
+
+    #[test]
+    fn keyed_by_all_parameters() {
+        let _serial = serial();
+        let crc32 = table_for(crc32());
+        let bzip2 = table_for(CrcParams { refin: false, refout: false, ..crc32.params() });
+        let jamcrc = table_for(CrcParams { xorout: 0, ..crc32.params() });
+        assert!(!Arc::ptr_eq(&crc32, &bzip2));
+        assert!(!Arc::ptr_eq(&crc32, &jamcrc));
+        assert_eq!(bzip2.check(), 0xFC891918);
+        assert_eq!(jamcrc.check(), 0x340BC6D9);
+
+        let usize_crc32 = table_for(CrcParams { width: 32, poly: 0x04C11DB7usize, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF });
+        assert_eq!(usize_crc32.check(), 0xCBF43926);
+        let crc82 = table_for(CrcParams { width: 82, poly: 0x0308C0111011401440411u128, init: 0, refin: true, refout: true, xorout: 0 });
+        assert_eq!(crc82.check(), 0x09EA83F625023801FD612);
+    }
+
+    #[test]
+    fn cleared() {
+        let _serial = serial();
+        let before = table_for(crc32());
+        let xmodem = CrcParams { width: 16, poly: 0x1021u16, init: 0, refin: false, refout: false, xorout: 0 };
+        table_for(xmodem);
+        assert!(len::<u32>() >= 1);
+
+        clear_for::<u32>();
+        assert_eq!(len::<u32>(), 0);
+        assert!(len::<u16>() >= 1);
+        let after = table_for(crc32());
+        assert!(!Arc::ptr_eq(&before, &after));
+        assert!(before == after);
+        assert_eq!(&before.table()[..], &after.table()[..]);
+
+        clear();
+        assert_eq!(len::<u32>(), 0);
+        assert_eq!(len::<u16>(), 0);
+    }
+
+    #[test]
+    fn invalid_params() {
+        let _serial = serial();
+        clear_for::<u32>();
+        assert_eq!(try_table_for(CrcParams { poly: 0, ..crc32() }).err(), Some(CrcError::ZeroPoly));
+        assert_eq!(len::<u32>(), 0);
+    }
+}
@@ -0,0 +1,1742 @@
+//! Ready-made specifications of commonly used CRC algorithms.
+//!
+//! The parameters are taken from the
+//! [catalogue of parametrised CRC algorithms](http://reveng.sourceforge.net/crc-catalogue/)
+//! maintained by Greg Cook.
+//!
+//! Each entry is a `CrcTable`, so besides computing checksums with it, its parameters
+//! can be read through the [`CrcSpec`](../trait.CrcSpec.html) accessors, e. g. for
+//! configuring a hasher of a different kind, or a hardware CRC unit.
+//!
+//! The algorithms are grouped into families by the type backing them, each compiled only
+//! with its cargo feature: `catalog-crc8`, `catalog-crc16`, `catalog-crc32` or `catalog-crc64`.
+//! All of them are enabled by default, through the `catalog-full` feature.
+//!
+//! The algorithms can also be enumerated with [`all`](fn.all.html), looked up by name with
+//! [`from_name`](fn.from_name.html), recognized by their parameters with
+//! [`identify`](fn.identify.html) or by their check value with
+//! [`identify_by_check`](fn.identify_by_check.html). Programs that choose the algorithm
+//! at run time can get a type-erased hasher for it from [`dyn_hasher`](fn.dyn_hasher.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use crc_rocksoft::*;
+//! use crc_rocksoft::primitive::*;
+//!
+//! let mut hasher = CrcTableHasher::from(catalog::crc32_iso_hdlc());
+//! hasher.update_from_slice(b"123456789");
+//! assert_eq!(hasher.finish(), 0xCBF43926);
+//! ```
+
+use ::{CrcSpec, CrcHasher, CrcBitInput};
+use primitive::{CrcTable, CrcTableHasher, ValueType};
+use core::fmt;
+#[cfg(feature = "alloc")] use primitive::CrcError;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+#[cfg(feature = "alloc")] use alloc::boxed::Box;
+#[cfg(feature = "alloc")] use alloc::string::{String, ToString};
+#[cfg(feature = "std")] use std::{error, io};
+
+
+/// Defines a constructor function for each algorithm, and its entry in `ENTRIES`.
+/// The algorithms must be sorted by their RevEng name, which comes first in the list of names.
+///
+/// Both are compiled only with the `catalog-*` feature of the algorithm's family, which is
+/// chosen by the type backing it. The entry of an algorithm that is compiled out is `None`;
+/// it lives in a private module named after the constructor, since modules and functions
+/// don't share a namespace.
+macro_rules! catalog {
+    ($(
+        $(#[$meta:meta])*
+        $entry:ident: $t:ident = [$($name:expr),+] {
+            width: $width:expr, poly: $poly:expr, init: $init:expr,
+            refin: $refin:expr, refout: $refout:expr, xorout: $xorout:expr, check: $check:expr
+        }
+    )*) => {
+        $(
+            catalog!(@family $t [
+                $(#[$meta])*
+                pub fn $entry() -> CrcTable<$t> {
+                    CrcTable::new_with_width($width, $poly, $init, $refin, $refout, $xorout)
+                }
+
+                mod $entry {
+                    use super::{CatalogEntry, Names};
+
+                    pub const ENTRY: Option<CatalogEntry> = Some(CatalogEntry {
+                        names: Names::Static(&[$($name),+]),
+                        width: $width, poly: $poly, init: $init,
+                        refin: $refin, refout: $refout, xorout: $xorout, check: $check
+                    });
+                }
+            ] [
+                mod $entry {
+                    pub const ENTRY: Option<super::CatalogEntry> = None;
+                }
+            ]);
+        )*
+
+        static ENTRIES: &[Option<CatalogEntry>] = &[$($entry::ENTRY),*];
+    };
+    (@family u8 $enabled:tt $disabled:tt) => { catalog!(@cfg "catalog-crc8" $enabled $disabled); };
+    (@family u16 $enabled:tt $disabled:tt) => { catalog!(@cfg "catalog-crc16" $enabled $disabled); };
+    (@family u32 $enabled:tt $disabled:tt) => { catalog!(@cfg "catalog-crc32" $enabled $disabled); };
+    (@family u64 $enabled:tt $disabled:tt) => { catalog!(@cfg "catalog-crc64" $enabled $disabled); };
+    (@cfg $feature:tt [$($enabled:item)*] [$($disabled:item)*]) => {
+        $(#[cfg(feature = $feature)] $enabled)*
+        $(#[cfg(not(feature = $feature))] $disabled)*
+    };
+}
+
+catalog! {
+    /// CRC-10/ATM (CRC-10/I-610), used in the ATM OAM cells.
+    crc10_atm: u16 = ["CRC-10/ATM", "CRC-10/I-610", "CRC-10"] {
+        width: 10, poly: 0x233, init: 0x000, refin: false, refout: false, xorout: 0x000, check: 0x199
+    }
+
+    /// CRC-10/CDMA2000, used in CDMA mobile networks.
+    crc10_cdma2000: u16 = ["CRC-10/CDMA2000"] {
+        width: 10, poly: 0x3D9, init: 0x3FF, refin: false, refout: false, xorout: 0x000, check: 0x233
+    }
+
+    /// CRC-10/GSM, used in GSM mobile networks.
+    crc10_gsm: u16 = ["CRC-10/GSM"] {
+        width: 10, poly: 0x175, init: 0x000, refin: false, refout: false, xorout: 0x3FF, check: 0x12A
+    }
+
+    /// CRC-11/FLEXRAY, used in the header of FlexRay frames.
+    crc11_flexray: u16 = ["CRC-11/FLEXRAY", "CRC-11"] {
+        width: 11, poly: 0x385, init: 0x01A, refin: false, refout: false, xorout: 0x000, check: 0x5A3
+    }
+
+    /// CRC-11/UMTS, used in UMTS mobile networks.
+    crc11_umts: u16 = ["CRC-11/UMTS"] {
+        width: 11, poly: 0x307, init: 0x000, refin: false, refout: false, xorout: 0x000, check: 0x061
+    }
+
+    /// CRC-12/CDMA2000, used in CDMA mobile networks.
+    crc12_cdma2000: u16 = ["CRC-12/CDMA2000"] {
+        width: 12, poly: 0xF13, init: 0xFFF, refin: false, refout: false, xorout: 0x000, check: 0xD4D
+    }
+
+    /// CRC-12/DECT, used in DECT cordless phones.
+    crc12_dect: u16 = ["CRC-12/DECT", "X-CRC-12"] {
+        width: 12, poly: 0x80F, init: 0x000, refin: false, refout: false, xorout: 0x000, check: 0xF5B
+    }
+
+    /// CRC-12/GSM, used in GSM mobile networks.
+    crc12_gsm: u16 = ["CRC-12/GSM"] {
+        width: 12, poly: 0xD31, init: 0x000, refin: false, refout: false, xorout: 0xFFF, check: 0xB34
+    }
+
+    /// CRC-12/UMTS (CRC-12/3GPP), used in UMTS mobile networks.
+    crc12_umts: u16 = ["CRC-12/UMTS", "CRC-12/3GPP"] {
+        width: 12, poly: 0x80F, init: 0x000, refin: false, refout: true, xorout: 0x000, check: 0xDAF
+    }
+
+    /// CRC-13/BBC, used in BBC time signals.
+    crc13_bbc: u16 = ["CRC-13/BBC"] {
+        width: 13, poly: 0x1CF5, init: 0x0000, refin: false, refout: false, xorout: 0x0000, check: 0x04FA
+    }
+
+    /// CRC-14/DARC, used in Data Radio Channel broadcasting.
+    crc14_darc: u16 = ["CRC-14/DARC"] {
+        width: 14, poly: 0x0805, init: 0x0000, refin: true, refout: true, xorout: 0x0000, check: 0x082D
+    }
+
+    /// CRC-14/GSM, used in GSM mobile networks.
+    crc14_gsm: u16 = ["CRC-14/GSM"] {
+        width: 14, poly: 0x202D, init: 0x0000, refin: false, refout: false, xorout: 0x3FFF, check: 0x30AE
+    }
+
+    /// CRC-15/CAN, used in Controller Area Network frames.
+    crc15_can: u16 = ["CRC-15/CAN", "CRC-15"] {
+        width: 15, poly: 0x4599, init: 0x0000, refin: false, refout: false, xorout: 0x0000, check: 0x059E
+    }
+
+    /// CRC-15/MPT1327, used in MPT 1327 trunked radio.
+    crc15_mpt1327: u16 = ["CRC-15/MPT1327"] {
+        width: 15, poly: 0x6815, init: 0x0000, refin: false, refout: false, xorout: 0x0001, check: 0x2566
+    }
+
+    /// CRC-16/ARC, used in the ARC archiver and LHA. Also known as plain CRC-16 and CRC-16/LHA.
+    crc16_arc: u16 = ["CRC-16/ARC", "CRC-16/LHA", "CRC-16", "CRC-IBM", "ARC"] {
+        width: 16, poly: 0x8005, init: 0x0000, refin: true, refout: true, xorout: 0x0000, check: 0xBB3D
+    }
+
+    /// CRC-16/DNP, used in the DNP3 protocol.
+    crc16_dnp: u16 = ["CRC-16/DNP"] {
+        width: 16, poly: 0x3D65, init: 0x0000, refin: true, refout: true, xorout: 0xFFFF, check: 0xEA82
+    }
+
+    /// CRC-16/GENIBUS, used in the Grundfos GENIbus protocol. Also known as CRC-16/EPC and CRC-16/DARC.
+    crc16_genibus: u16 = ["CRC-16/GENIBUS", "CRC-16/DARC", "CRC-16/EPC", "CRC-16/EPC-C1G2", "CRC-16/I-CODE"] {
+        width: 16, poly: 0x1021, init: 0xFFFF, refin: false, refout: false, xorout: 0xFFFF, check: 0xD64E
+    }
+
+    /// CRC-16/CCITT-FALSE (CRC-16/IBM-3740 in the RevEng catalogue).
+    crc16_ccitt_false: u16 = ["CRC-16/IBM-3740", "CRC-16/CCITT-FALSE", "CRC-16/AUTOSAR"] {
+        width: 16, poly: 0x1021, init: 0xFFFF, refin: false, refout: false, xorout: 0x0000, check: 0x29B1
+    }
+
+    /// CRC-16/IBM-SDLC, used in HDLC, X.25 and many more. Also known as CRC-16/X-25 and CRC-16/ISO-HDLC.
+    crc16_ibm_sdlc: u16 = ["CRC-16/IBM-SDLC", "CRC-16/ISO-HDLC", "CRC-16/X-25", "CRC-B", "X-25"] {
+        width: 16, poly: 0x1021, init: 0xFFFF, refin: true, refout: true, xorout: 0xFFFF, check: 0x906E
+    }
+
+    /// CRC-16/KERMIT, used in the Kermit protocol. Also known as CRC-16/CCITT (or CRC-CCITT).
+    /// It's [CRC-16/XMODEM](fn.crc16_xmodem.html) with reflected input and output.
+    crc16_kermit: u16 = ["CRC-16/KERMIT", "CRC-16/CCITT", "CRC-16/CCITT-TRUE", "CRC-16/V-41-LSB", "CRC-CCITT", "KERMIT"] {
+        width: 16, poly: 0x1021, init: 0x0000, refin: true, refout: true, xorout: 0x0000, check: 0x2189
+    }
+
+    /// CRC-16/MAXIM (CRC-16/MAXIM-DOW in the RevEng catalogue), used in Maxim 1-Wire devices.
+    crc16_maxim: u16 = ["CRC-16/MAXIM-DOW", "CRC-16/MAXIM"] {
+        width: 16, poly: 0x8005, init: 0x0000, refin: true, refout: true, xorout: 0xFFFF, check: 0x44C2
+    }
+
+    /// CRC-16/MCRF4XX, used in Microchip MCRF4xx RFID tags.
+    crc16_mcrf4xx: u16 = ["CRC-16/MCRF4XX"] {
+        width: 16, poly: 0x1021, init: 0xFFFF, refin: true, refout: true, xorout: 0x0000, check: 0x6F91
+    }
+
+    /// CRC-16/MODBUS, used in the Modbus protocol.
+    crc16_modbus: u16 = ["CRC-16/MODBUS", "MODBUS"] {
+        width: 16, poly: 0x8005, init: 0xFFFF, refin: true, refout: true, xorout: 0x0000, check: 0x4B37
+    }
+
+    /// CRC-16/T10-DIF, used in the SCSI Data Integrity Field.
+    crc16_t10_dif: u16 = ["CRC-16/T10-DIF"] {
+        width: 16, poly: 0x8BB7, init: 0x0000, refin: false, refout: false, xorout: 0x0000, check: 0xD0DB
+    }
+
+    /// CRC-16/USB, used in USB data packets.
+    crc16_usb: u16 = ["CRC-16/USB"] {
+        width: 16, poly: 0x8005, init: 0xFFFF, refin: true, refout: true, xorout: 0xFFFF, check: 0xB4C8
+    }
+
+    /// CRC-16/XMODEM, used in the XMODEM protocol. Also known as CRC-16/ACORN and CRC-16/LTE.
+    /// It's [CRC-16/KERMIT](fn.crc16_kermit.html) without reflection.
+    crc16_xmodem: u16 = ["CRC-16/XMODEM", "CRC-16/ACORN", "CRC-16/LTE", "CRC-16/V-41-MSB", "XMODEM", "ZMODEM"] {
+        width: 16, poly: 0x1021, init: 0x0000, refin: false, refout: false, xorout: 0x0000, check: 0x31C3
+    }
+
+    /// CRC-17/CAN-FD, used in CAN FD frames with up to 16 data bytes.
+    ///
+    /// As in the RevEng catalogue, `init` is zero. ISO 11898-1:2015 starts the register
+    /// with its top bit set instead; for that variant, use the same parameters with `init` of `0x10000`.
+    crc17_can_fd: u32 = ["CRC-17/CAN-FD"] {
+        width: 17, poly: 0x1685B, init: 0x00000, refin: false, refout: false, xorout: 0x00000, check: 0x04F03
+    }
+
+    /// CRC-21/CAN-FD, used in CAN FD frames with more than 16 data bytes.
+    ///
+    /// As in the RevEng catalogue, `init` is zero. ISO 11898-1:2015 starts the register
+    /// with its top bit set instead; for that variant, use the same parameters with `init` of `0x100000`.
+    crc21_can_fd: u32 = ["CRC-21/CAN-FD"] {
+        width: 21, poly: 0x102899, init: 0x000000, refin: false, refout: false, xorout: 0x000000, check: 0x0ED841
+    }
+
+    /// CRC-24/BLE, used in Bluetooth Low Energy link layer packets.
+    crc24_ble: u32 = ["CRC-24/BLE"] {
+        width: 24, poly: 0x00065B, init: 0x555555, refin: true, refout: true, xorout: 0x000000, check: 0xC25A56
+    }
+
+    /// CRC-24/FLEXRAY-A, used in the FlexRay automotive network (channel A).
+    crc24_flexray_a: u32 = ["CRC-24/FLEXRAY-A"] {
+        width: 24, poly: 0x5D6DCB, init: 0xFEDCBA, refin: false, refout: false, xorout: 0x000000, check: 0x7979BD
+    }
+
+    /// CRC-24/FLEXRAY-B, used in the FlexRay automotive network (channel B).
+    crc24_flexray_b: u32 = ["CRC-24/FLEXRAY-B"] {
+        width: 24, poly: 0x5D6DCB, init: 0xABCDEF, refin: false, refout: false, xorout: 0x000000, check: 0x1F23B8
+    }
+
+    /// CRC-24/INTERLAKEN, used in the Interlaken chip-to-chip interface.
+    crc24_interlaken: u32 = ["CRC-24/INTERLAKEN"] {
+        width: 24, poly: 0x328B63, init: 0xFFFFFF, refin: false, refout: false, xorout: 0xFFFFFF, check: 0xB4F3E6
+    }
+
+    /// CRC-24/LTE-A, used in LTE transport blocks.
+    crc24_lte_a: u32 = ["CRC-24/LTE-A"] {
+        width: 24, poly: 0x864CFB, init: 0x000000, refin: false, refout: false, xorout: 0x000000, check: 0xCDE703
+    }
+
+    /// CRC-24/LTE-B, used in LTE code blocks.
+    crc24_lte_b: u32 = ["CRC-24/LTE-B"] {
+        width: 24, poly: 0x800063, init: 0x000000, refin: false, refout: false, xorout: 0x000000, check: 0x23EF52
+    }
+
+    /// CRC-24/OPENPGP, used in the ASCII armor of OpenPGP messages.
+    crc24_openpgp: u32 = ["CRC-24/OPENPGP", "CRC-24"] {
+        width: 24, poly: 0x864CFB, init: 0xB704CE, refin: false, refout: false, xorout: 0x000000, check: 0x21CF02
+    }
+
+    /// CRC-24/OS-9, used in OS-9 executable modules.
+    crc24_os_9: u32 = ["CRC-24/OS-9"] {
+        width: 24, poly: 0x800063, init: 0xFFFFFF, refin: false, refout: false, xorout: 0xFFFFFF, check: 0x200FA5
+    }
+
+    /// CRC-3/GSM, used in GSM mobile networks.
+    crc3_gsm: u8 = ["CRC-3/GSM"] {
+        width: 3, poly: 0x3, init: 0x0, refin: false, refout: false, xorout: 0x7, check: 0x4
+    }
+
+    /// CRC-3/ROHC, used in Robust Header Compression.
+    crc3_rohc: u8 = ["CRC-3/ROHC"] {
+        width: 3, poly: 0x3, init: 0x7, refin: true, refout: true, xorout: 0x0, check: 0x6
+    }
+
+    /// CRC-32Q (CRC-32/AIXM in the RevEng catalogue), used in aeronautical information exchange.
+    crc32q: u32 = ["CRC-32/AIXM", "CRC-32Q"] {
+        width: 32, poly: 0x814141AB, init: 0x00000000, refin: false, refout: false, xorout: 0x00000000, check: 0x3010BF7F
+    }
+
+    /// CRC-32/AUTOSAR, specified by AUTOSAR for automotive software.
+    crc32_autosar: u32 = ["CRC-32/AUTOSAR"] {
+        width: 32, poly: 0xF4ACFB13, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF, check: 0x1697D06A
+    }
+
+    /// CRC-32D (CRC-32/BASE91-D in the RevEng catalogue), used in the BASE91 encoding.
+    crc32d: u32 = ["CRC-32/BASE91-D", "CRC-32D"] {
+        width: 32, poly: 0xA833982B, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF, check: 0x87315576
+    }
+
+    /// CRC-32/BZIP2, used in bzip2. Also known as CRC-32/AAL5 and CRC-32/DECT-B.
+    crc32_bzip2: u32 = ["CRC-32/BZIP2", "CRC-32/AAL5", "CRC-32/DECT-B", "B-CRC-32"] {
+        width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: false, refout: false, xorout: 0xFFFFFFFF, check: 0xFC891918
+    }
+
+    /// CRC-32/POSIX (CRC-32/CKSUM in the RevEng catalogue), used in the POSIX `cksum` utility
+    /// (which also appends the message length to the message).
+    crc32_posix: u32 = ["CRC-32/CKSUM", "CRC-32/POSIX", "CKSUM"] {
+        width: 32, poly: 0x04C11DB7, init: 0x00000000, refin: false, refout: false, xorout: 0xFFFFFFFF, check: 0x765E7680
+    }
+
+    /// CRC-32/ISCSI, used in iSCSI, SCTP, ext4 and Btrfs. Also known as CRC-32C (Castagnoli).
+    crc32_iscsi: u32 = ["CRC-32/ISCSI", "CRC-32/BASE91-C", "CRC-32/CASTAGNOLI", "CRC-32/INTERLAKEN", "CRC-32C", "CRC32C"] {
+        width: 32, poly: 0x1EDC6F41, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF, check: 0xE3069283
+    }
+
+    /// CRC-32/ISO-HDLC, the most common CRC-32 variant, used in Ethernet, zlib, PNG and many more.
+    /// Also known as plain CRC-32.
+    crc32_iso_hdlc: u32 = ["CRC-32/ISO-HDLC", "CRC-32/ADCCP", "CRC-32/V-42", "CRC-32/XZ", "CRC-32", "CRC32", "PKZIP"] {
+        width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF, check: 0xCBF43926
+    }
+
+    /// CRC-32/JAMCRC, used in Altera FPGA tools. The same as CRC-32/ISO-HDLC without the final XOR.
+    crc32_jamcrc: u32 = ["CRC-32/JAMCRC", "JAMCRC"] {
+        width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0x00000000, check: 0x340BC6D9
+    }
+
+    /// CRC-32/MPEG-2, used in MPEG-2 transport streams.
+    crc32_mpeg_2: u32 = ["CRC-32/MPEG-2"] {
+        width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: false, refout: false, xorout: 0x00000000, check: 0x0376E6E7
+    }
+
+    /// CRC-32/XFER, used in the XFER file transfer protocol.
+    crc32_xfer: u32 = ["CRC-32/XFER"] {
+        width: 32, poly: 0x000000AF, init: 0x00000000, refin: false, refout: false, xorout: 0x00000000, check: 0xBD0BE338
+    }
+
+    /// CRC-4/G-704 (CRC-4/ITU), used in E1 framing per ITU-T G.704.
+    crc4_g_704: u8 = ["CRC-4/G-704", "CRC-4/ITU"] {
+        width: 4, poly: 0x3, init: 0x0, refin: true, refout: true, xorout: 0x0, check: 0x7
+    }
+
+    /// CRC-4/INTERLAKEN, used in the Interlaken chip-to-chip interface.
+    crc4_interlaken: u8 = ["CRC-4/INTERLAKEN"] {
+        width: 4, poly: 0x3, init: 0xF, refin: false, refout: false, xorout: 0xF, check: 0xB
+    }
+
+    /// CRC-5/EPC-C1G2, used in EPC Gen 2 RFID tags.
+    crc5_epc_c1g2: u8 = ["CRC-5/EPC-C1G2", "CRC-5/EPC"] {
+        width: 5, poly: 0x09, init: 0x09, refin: false, refout: false, xorout: 0x00, check: 0x00
+    }
+
+    /// CRC-5/G-704 (CRC-5/ITU), used in ITU-T G.704 framing.
+    crc5_g_704: u8 = ["CRC-5/G-704", "CRC-5/ITU"] {
+        width: 5, poly: 0x15, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0x07
+    }
+
+    /// CRC-5/USB, used in USB token packets.
+    crc5_usb: u8 = ["CRC-5/USB"] {
+        width: 5, poly: 0x05, init: 0x1F, refin: true, refout: true, xorout: 0x1F, check: 0x19
+    }
+
+    /// CRC-6/CDMA2000-A, used in CDMA mobile networks.
+    crc6_cdma2000_a: u8 = ["CRC-6/CDMA2000-A"] {
+        width: 6, poly: 0x27, init: 0x3F, refin: false, refout: false, xorout: 0x00, check: 0x0D
+    }
+
+    /// CRC-6/CDMA2000-B, used in CDMA mobile networks.
+    crc6_cdma2000_b: u8 = ["CRC-6/CDMA2000-B"] {
+        width: 6, poly: 0x07, init: 0x3F, refin: false, refout: false, xorout: 0x00, check: 0x3B
+    }
+
+    /// CRC-6/DARC, used in Data Radio Channel broadcasting.
+    crc6_darc: u8 = ["CRC-6/DARC"] {
+        width: 6, poly: 0x19, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0x26
+    }
+
+    /// CRC-6/G-704 (CRC-6/ITU), used in ITU-T G.704 framing.
+    crc6_g_704: u8 = ["CRC-6/G-704", "CRC-6/ITU"] {
+        width: 6, poly: 0x03, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0x06
+    }
+
+    /// CRC-64/ECMA-182, specified in ECMA-182 for DLT-1 tape cartridges.
+    crc64_ecma_182: u64 = ["CRC-64/ECMA-182", "CRC-64"] {
+        width: 64, poly: 0x42F0E1EBA9EA3693, init: 0x0000000000000000, refin: false, refout: false, xorout: 0x0000000000000000, check: 0x6C40DF5F0B497347
+    }
+
+    /// CRC-64/MS, used in Microsoft's Extensible Storage Engine.
+    crc64_ms: u64 = ["CRC-64/MS"] {
+        width: 64, poly: 0x259C84CBA6426349, init: 0xFFFFFFFFFFFFFFFF, refin: true, refout: true, xorout: 0x0000000000000000, check: 0x75D4B74F024ECEEA
+    }
+
+    /// CRC-64/REDIS, used in Redis.
+    crc64_redis: u64 = ["CRC-64/REDIS"] {
+        width: 64, poly: 0xAD93D23594C935A9, init: 0x0000000000000000, refin: true, refout: true, xorout: 0x0000000000000000, check: 0xE9C6D914C4B8D9CA
+    }
+
+    /// CRC-64/WE, the ECMA-182 polynomial with all-ones `init` and `xorout`.
+    crc64_we: u64 = ["CRC-64/WE"] {
+        width: 64, poly: 0x42F0E1EBA9EA3693, init: 0xFFFFFFFFFFFFFFFF, refin: false, refout: false, xorout: 0xFFFFFFFFFFFFFFFF, check: 0x62EC59E3F1A4F00A
+    }
+
+    /// CRC-64/XZ, used in xz and liblzma. Also known as CRC-64/GO-ECMA.
+    /// This is usually the one meant by plain CRC-64.
+    crc64_xz: u64 = ["CRC-64/XZ", "CRC-64/GO-ECMA"] {
+        width: 64, poly: 0x42F0E1EBA9EA3693, init: 0xFFFFFFFFFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFFFFFFFFFF, check: 0x995DC9BBDF1939FA
+    }
+
+    /// CRC-7/MMC, used in MultiMediaCard and SD card commands.
+    crc7_mmc: u8 = ["CRC-7/MMC", "CRC-7"] {
+        width: 7, poly: 0x09, init: 0x00, refin: false, refout: false, xorout: 0x00, check: 0x75
+    }
+
+    /// CRC-7/ROHC, used in Robust Header Compression.
+    crc7_rohc: u8 = ["CRC-7/ROHC"] {
+        width: 7, poly: 0x4F, init: 0x7F, refin: true, refout: true, xorout: 0x00, check: 0x53
+    }
+
+    /// CRC-7/UMTS, used in UMTS mobile networks.
+    crc7_umts: u8 = ["CRC-7/UMTS"] {
+        width: 7, poly: 0x45, init: 0x00, refin: false, refout: false, xorout: 0x00, check: 0x61
+    }
+
+    /// CRC-8/AUTOSAR, specified by AUTOSAR for automotive software.
+    crc8_autosar: u8 = ["CRC-8/AUTOSAR"] {
+        width: 8, poly: 0x2F, init: 0xFF, refin: false, refout: false, xorout: 0xFF, check: 0xDF
+    }
+
+    /// CRC-8/BLUETOOTH, used in the Bluetooth header error check.
+    crc8_bluetooth: u8 = ["CRC-8/BLUETOOTH"] {
+        width: 8, poly: 0xA7, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0x26
+    }
+
+    /// CRC-8/CDMA2000, used in CDMA mobile networks.
+    crc8_cdma2000: u8 = ["CRC-8/CDMA2000"] {
+        width: 8, poly: 0x9B, init: 0xFF, refin: false, refout: false, xorout: 0x00, check: 0xDA
+    }
+
+    /// CRC-8/DARC, used in Data Radio Channel broadcasting.
+    crc8_darc: u8 = ["CRC-8/DARC"] {
+        width: 8, poly: 0x39, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0x15
+    }
+
+    /// CRC-8/DVB-S2, used in DVB-S2 satellite broadcasting.
+    crc8_dvb_s2: u8 = ["CRC-8/DVB-S2"] {
+        width: 8, poly: 0xD5, init: 0x00, refin: false, refout: false, xorout: 0x00, check: 0xBC
+    }
+
+    /// CRC-8/ITU (CRC-8/I-432-1 in the RevEng catalogue), used in the ATM header error control.
+    crc8_itu: u8 = ["CRC-8/I-432-1", "CRC-8/ITU"] {
+        width: 8, poly: 0x07, init: 0x00, refin: false, refout: false, xorout: 0x55, check: 0xA1
+    }
+
+    /// CRC-8/I-CODE, used in Philips I-CODE RFID tags.
+    crc8_i_code: u8 = ["CRC-8/I-CODE"] {
+        width: 8, poly: 0x1D, init: 0xFD, refin: false, refout: false, xorout: 0x00, check: 0x7E
+    }
+
+    /// CRC-8/MAXIM (CRC-8/MAXIM-DOW in the RevEng catalogue), used in Maxim 1-Wire devices.
+    crc8_maxim: u8 = ["CRC-8/MAXIM-DOW", "CRC-8/MAXIM", "DOW-CRC"] {
+        width: 8, poly: 0x31, init: 0x00, refin: true, refout: true, xorout: 0x00, check: 0xA1
+    }
+
+    /// CRC-8/NRSC-5, used in HD Radio.
+    crc8_nrsc_5: u8 = ["CRC-8/NRSC-5"] {
+        width: 8, poly: 0x31, init: 0xFF, refin: false, refout: false, xorout: 0x00, check: 0xF7
+    }
+
+    /// CRC-8/ROHC, used in Robust Header Compression.
+    crc8_rohc: u8 = ["CRC-8/ROHC"] {
+        width: 8, poly: 0x07, init: 0xFF, refin: true, refout: true, xorout: 0x00, check: 0xD0
+    }
+
+    /// CRC-8/SMBUS, used in the System Management Bus. Also known as plain CRC-8.
+    crc8_smbus: u8 = ["CRC-8/SMBUS", "CRC-8"] {
+        width: 8, poly: 0x07, init: 0x00, refin: false, refout: false, xorout: 0x00, check: 0xF4
+    }
+}
+
+/// CRC-16/X-25, the same algorithm as [`crc16_ibm_sdlc`](fn.crc16_ibm_sdlc.html).
+#[cfg(feature = "catalog-crc16")]
+pub fn crc16_x25() -> CrcTable<u16> {
+    crc16_ibm_sdlc()
+}
+
+/// CRC-32C (Castagnoli), the same algorithm as [`crc32_iscsi`](fn.crc32_iscsi.html).
+#[cfg(feature = "catalog-crc32")]
+pub fn crc32c() -> CrcTable<u32> {
+    crc32_iscsi()
+}
+
+/// The description of an algorithm in the catalog: its names, parameters and check value.
+///
+/// The parameters are available through the [`CrcSpec`](../trait.CrcSpec.html) accessors,
+/// as `u64` values; use [`spec`](#method.spec) or [`hasher`](#method.hasher) for computing checksums.
+///
+/// ```
+/// use crc_rocksoft::*;
+///
+/// for entry in catalog::all() {
+///     println!("{:<20} {:>2} {:#x}", entry.name(), entry.width(), entry.check());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CatalogEntry {
+    names: Names,
+    width: usize,
+    poly: u64,
+    init: u64,
+    refin: bool,
+    refout: bool,
+    xorout: u64,
+    check: u64,
+}
+
+impl CatalogEntry {
+    /// Returns the name of the algorithm in the RevEng catalogue.
+    pub fn name(&self) -> &str {
+        match self.names {
+            Names::Static(names) => names[0],
+            #[cfg(feature = "alloc")]
+            Names::Owned(ref name) => name,
+        }
+    }
+
+    /// Returns the other names the algorithm is known by, which [`from_name`](fn.from_name.html)
+    /// also accepts. Entries loaded with [`parse_reveng_line`](fn.parse_reveng_line.html) have none.
+    pub fn aliases(&self) -> &[&'static str] {
+        match self.names {
+            Names::Static(names) => &names[1..],
+            #[cfg(feature = "alloc")]
+            Names::Owned(_) => &[],
+        }
+    }
+
+    /// Returns the width of the algorithm. The same as `CrcSpec::width`,
+    /// but usable without importing the trait.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the checksum of the ASCII string `"123456789"`, as listed in the RevEng catalogue.
+    pub fn check(&self) -> u64 {
+        self.check
+    }
+
+    /// Returns the [residue](../primitive/struct.CrcTable.html#method.residue) of the algorithm.
+    pub fn residue(&self) -> u64 {
+        match self.spec() {
+            CatalogSpec::U8(spec) => u64::from(spec.residue()),
+            CatalogSpec::U16(spec) => u64::from(spec.residue()),
+            CatalogSpec::U32(spec) => u64::from(spec.residue()),
+            CatalogSpec::U64(spec) => spec.residue(),
+        }
+    }
+
+    /// Builds the spec of the algorithm, backed by the narrowest primitive type that fits its width.
+    pub fn spec(&self) -> CatalogSpec {
+        let (width, refin, refout) = (self.width, self.refin, self.refout);
+        match width {
+            0..=8 => CatalogSpec::U8(CrcTable::new_with_width(
+                width, self.poly as u8, self.init as u8, refin, refout, self.xorout as u8)),
+            9..=16 => CatalogSpec::U16(CrcTable::new_with_width(
+                width, self.poly as u16, self.init as u16, refin, refout, self.xorout as u16)),
+            17..=32 => CatalogSpec::U32(CrcTable::new_with_width(
+                width, self.poly as u32, self.init as u32, refin, refout, self.xorout as u32)),
+            _ => CatalogSpec::U64(CrcTable::new_with_width(
+                width, self.poly, self.init, refin, refout, self.xorout)),
+        }
+    }
+
+    /// Builds a hasher for the algorithm.
+    pub fn hasher(&self) -> CatalogHasher {
+        match self.spec() {
+            CatalogSpec::U8(spec) => CatalogHasher::U8(CrcTableHasher::from(spec)),
+            CatalogSpec::U16(spec) => CatalogHasher::U16(CrcTableHasher::from(spec)),
+            CatalogSpec::U32(spec) => CatalogHasher::U32(CrcTableHasher::from(spec)),
+            CatalogSpec::U64(spec) => CatalogHasher::U64(CrcTableHasher::from(spec)),
+        }
+    }
+}
+
+/// The names of an entry, the RevEng name first.
+/// Entries parsed at run time have only the RevEng name, which is owned.
+#[derive(Debug)]
+enum Names {
+    // Unused when every family is compiled out.
+    #[allow(dead_code)]
+    Static(&'static [&'static str]),
+    #[cfg(feature = "alloc")]
+    Owned(String),
+}
+
+impl CrcSpec<u64> for CatalogEntry {
+    fn width(&self) -> usize { self.width }
+    fn poly(&self) -> u64 { self.poly }
+    fn init(&self) -> u64 { self.init }
+    fn refin(&self) -> bool { self.refin }
+    fn refout(&self) -> bool { self.refout }
+    fn xorout(&self) -> u64 { self.xorout }
+}
+
+/// Prints the algorithm as a line of the RevEng catalogue, which can be parsed back with
+/// [`parse_reveng_line`](fn.parse_reveng_line.html). Aliases are omitted.
+///
+/// ```
+/// use crc_rocksoft::catalog;
+///
+/// assert_eq!(
+///     catalog::from_name("CRC-16/ARC").unwrap().to_string(),
+///     "width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0000 name=\"CRC-16/ARC\""
+/// );
+/// ```
+impl fmt::Display for CatalogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} name=\"{}\"", self.spec(), self.name())
+    }
+}
+
+/// The spec of a [`CatalogEntry`](struct.CatalogEntry.html).
+// The variants differ in size only by the size of their tables, and can't be boxed without `alloc`.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum CatalogSpec {
+    /// A spec backed by `u8`.
+    U8(CrcTable<u8>),
+    /// A spec backed by `u16`.
+    U16(CrcTable<u16>),
+    /// A spec backed by `u32`.
+    U32(CrcTable<u32>),
+    /// A spec backed by `u64`.
+    U64(CrcTable<u64>),
+}
+
+/// Prints the algorithm in the format of the RevEng catalogue, as `CrcTable` does.
+impl fmt::Display for CatalogSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CatalogSpec::U8(ref spec) => fmt::Display::fmt(spec, f),
+            CatalogSpec::U16(ref spec) => fmt::Display::fmt(spec, f),
+            CatalogSpec::U32(ref spec) => fmt::Display::fmt(spec, f),
+            CatalogSpec::U64(ref spec) => fmt::Display::fmt(spec, f),
+        }
+    }
+}
+
+/// A hasher for a [`CatalogEntry`](struct.CatalogEntry.html).
+/// The checksum is zero-extended to `u64`.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum CatalogHasher {
+    /// A hasher backed by `u8`.
+    U8(CrcTableHasher<u8, CrcTable<u8>>),
+    /// A hasher backed by `u16`.
+    U16(CrcTableHasher<u16, CrcTable<u16>>),
+    /// A hasher backed by `u32`.
+    U32(CrcTableHasher<u32, CrcTable<u32>>),
+    /// A hasher backed by `u64`.
+    U64(CrcTableHasher<u64, CrcTable<u64>>),
+}
+
+impl CrcHasher<u64> for CatalogHasher {
+    fn reset(&mut self) {
+        match *self {
+            CatalogHasher::U8(ref mut hasher) => hasher.reset(),
+            CatalogHasher::U16(ref mut hasher) => hasher.reset(),
+            CatalogHasher::U32(ref mut hasher) => hasher.reset(),
+            CatalogHasher::U64(ref mut hasher) => hasher.reset(),
+        }
+    }
+
+    fn update(&mut self, byte: u8) {
+        match *self {
+            CatalogHasher::U8(ref mut hasher) => hasher.update(byte),
+            CatalogHasher::U16(ref mut hasher) => hasher.update(byte),
+            CatalogHasher::U32(ref mut hasher) => hasher.update(byte),
+            CatalogHasher::U64(ref mut hasher) => hasher.update(byte),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        match *self {
+            CatalogHasher::U8(ref hasher) => u64::from(hasher.finish()),
+            CatalogHasher::U16(ref hasher) => u64::from(hasher.finish()),
+            CatalogHasher::U32(ref hasher) => u64::from(hasher.finish()),
+            CatalogHasher::U64(ref hasher) => CrcHasher::finish(hasher),
+        }
+    }
+}
+
+impl CrcBitInput<u64> for CatalogHasher {
+    fn update_bit(&mut self, bit: bool) {
+        match *self {
+            CatalogHasher::U8(ref mut hasher) => hasher.update_bit(bit),
+            CatalogHasher::U16(ref mut hasher) => hasher.update_bit(bit),
+            CatalogHasher::U32(ref mut hasher) => hasher.update_bit(bit),
+            CatalogHasher::U64(ref mut hasher) => hasher.update_bit(bit),
+        }
+    }
+
+    fn update_last_bits(&mut self, byte: u8, nbits: u8) {
+        match *self {
+            CatalogHasher::U8(ref mut hasher) => hasher.update_last_bits(byte, nbits),
+            CatalogHasher::U16(ref mut hasher) => hasher.update_last_bits(byte, nbits),
+            CatalogHasher::U32(ref mut hasher) => hasher.update_last_bits(byte, nbits),
+            CatalogHasher::U64(ref mut hasher) => hasher.update_last_bits(byte, nbits),
+        }
+    }
+
+    fn refin(&self) -> bool {
+        match *self {
+            CatalogHasher::U8(ref hasher) => hasher.refin(),
+            CatalogHasher::U16(ref hasher) => hasher.refin(),
+            CatalogHasher::U32(ref hasher) => hasher.refin(),
+            CatalogHasher::U64(ref hasher) => hasher.refin(),
+        }
+    }
+}
+
+impl CatalogHasher {
+    /// Returns the width of the algorithm.
+    pub fn width(&self) -> usize {
+        match *self {
+            CatalogHasher::U8(ref hasher) => hasher.spec().width(),
+            CatalogHasher::U16(ref hasher) => hasher.spec().width(),
+            CatalogHasher::U32(ref hasher) => hasher.spec().width(),
+            CatalogHasher::U64(ref hasher) => hasher.spec().width(),
+        }
+    }
+}
+
+/// A hasher whose algorithm is chosen at run time, e. g. with [`dyn_hasher`](fn.dyn_hasher.html).
+/// Unlike `CrcHasher`, the trait doesn't depend on the type backing the algorithm,
+/// so hashers of any width can be used as trait objects.
+///
+/// Only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub trait DynCrcHasher {
+    /// Resets the hasher, as in [`CrcHasher::reset`](../trait.CrcHasher.html#tymethod.reset).
+    fn reset(&mut self);
+
+    /// Updates the hasher with one byte of user data.
+    fn update(&mut self, byte: u8);
+
+    /// Updates the hasher with all the bytes in the supplied slice.
+    fn update_from_slice(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.update(b);
+        }
+    }
+
+    /// Returns the width of the algorithm.
+    fn width(&self) -> usize;
+
+    /// Returns the checksum, zero-extended to `u64`, without modifying the internal state.
+    fn finish_u64(&self) -> u64;
+
+    /// Returns the checksum as bytes, most significant byte first, without modifying
+    /// the internal state. There are as many bytes as needed to fit the width of the algorithm.
+    fn finish_bytes(&self) -> Vec<u8> {
+        self.finish_u64().be_bytes(self.width()).to_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DynCrcHasher for CatalogHasher {
+    fn reset(&mut self) {
+        CrcHasher::reset(self)
+    }
+
+    fn update(&mut self, byte: u8) {
+        CrcHasher::update(self, byte)
+    }
+
+    fn update_from_slice(&mut self, bytes: &[u8]) {
+        CrcHasher::update_from_slice(self, bytes)
+    }
+
+    fn width(&self) -> usize {
+        CatalogHasher::width(self)
+    }
+
+    fn finish_u64(&self) -> u64 {
+        CrcHasher::finish(self)
+    }
+}
+
+/// Returns all the algorithms in the catalog, sorted by name.
+///
+/// Algorithms available under several constructor functions, like
+/// [`crc32c`](fn.crc32c.html) and [`crc32_iscsi`](fn.crc32_iscsi.html), are listed once.
+pub fn all() -> impl Iterator<Item = &'static CatalogEntry> {
+    ENTRIES.iter().filter_map(Option::as_ref)
+}
+
+/// Looks up an algorithm by its name in the RevEng catalogue, or by one of its aliases,
+/// such as `"CRC-32"`, `"CRC32C"`, `"CKSUM"`, `"XMODEM"` or `"MODBUS"`.
+///
+/// The lookup is case-insensitive, and `-`, `_` and `/` are considered the same character,
+/// so `"crc_32_bzip2"` finds CRC-32/BZIP2. Names without a family, like `"CRC-16"`,
+/// refer to the same algorithms as in the RevEng catalogue, e. g. CRC-16/ARC.
+///
+/// ```
+/// use crc_rocksoft::*;
+///
+/// let entry = catalog::from_name("crc-32/bzip2").unwrap();
+/// assert_eq!(entry.width(), 32);
+/// let mut hasher = entry.hasher();
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), 0xFC891918);
+///
+/// assert!(catalog::from_name("CRC-32/UNKNOWN").is_none());
+/// ```
+pub fn from_name(name: &str) -> Option<&'static CatalogEntry> {
+    all().find(|entry| {
+        names_match(entry.name(), name) || entry.aliases().iter().any(|known| names_match(known, name))
+    })
+}
+
+/// Looks up an algorithm by name, as in [`from_name`](fn.from_name.html),
+/// and returns a hasher for it that hides the type backing the algorithm.
+///
+/// Only available with the `alloc` feature.
+///
+/// ```
+/// use crc_rocksoft::catalog::{self, DynCrcHasher};
+///
+/// let mut hasher = catalog::dyn_hasher("CRC-16/MODBUS").unwrap();
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.width(), 16);
+/// assert_eq!(hasher.finish_u64(), 0x4B37);
+/// assert_eq!(hasher.finish_bytes(), [0x4B, 0x37]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn dyn_hasher(name: &str) -> Option<Box<dyn DynCrcHasher>> {
+    from_name(name).map(|entry| Box::new(entry.hasher()) as Box<dyn DynCrcHasher>)
+}
+
+/// Returns the RevEng name of the catalog algorithm with the same parameters as `spec`,
+/// if there is one.
+///
+/// The parameters are compared as values of the algorithm's width, so the type
+/// backing the spec doesn't matter: e. g. CRC-32 backed by a `u64` is still identified.
+/// Algorithms listed in the catalog under several names, like CRC-32C and CRC-32/ISCSI,
+/// are identified by the RevEng name.
+///
+/// ```
+/// use crc_rocksoft::catalog;
+/// use crc_rocksoft::primitive::CrcTable;
+///
+/// let spec = CrcTable::new(0x1021u16, 0xFFFFu16, true, true, 0u16);
+/// assert_eq!(catalog::identify(&spec), Some("CRC-16/MCRF4XX"));
+/// ```
+pub fn identify<T: ValueType>(spec: &impl CrcSpec<T>) -> Option<&'static str> {
+    all().find(|entry| same_parameters(*entry, spec)).map(CatalogEntry::name)
+}
+
+/// Returns the RevEng names of the catalog algorithms of the given width whose check value,
+/// i. e. the checksum of `"123456789"`, is `check`, in the order of [`all`](fn.all.html).
+///
+/// This only searches the catalog; unknown algorithms aren't guessed. Several algorithms
+/// can share a check value by chance, so the result is a list of candidates.
+///
+/// Only available with the `alloc` feature.
+///
+/// ```
+/// use crc_rocksoft::catalog;
+///
+/// assert_eq!(catalog::identify_by_check(16, 0x4B37), ["CRC-16/MODBUS"]);
+/// assert!(catalog::identify_by_check(16, 0x1234).is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn identify_by_check(width: usize, check: u64) -> Vec<&'static str> {
+    all().filter(|entry| entry.width() == width && entry.check() == check).map(CatalogEntry::name).collect()
+}
+
+/// A discrepancy found by [`self_test`](fn.self_test.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfTestFailure {
+    /// The RevEng name of the algorithm.
+    pub name: &'static str,
+    /// The value that differs: `"check"` or `"residue"`.
+    pub field: &'static str,
+    /// The value listed in the catalog, or computed without the lookup table.
+    pub expected: u64,
+    /// The value computed with the lookup table.
+    pub actual: u64,
+}
+
+/// Checks every algorithm in the catalog: builds its lookup table, and compares the checksum
+/// of `"123456789"` with the listed check value. For algorithms whose width is a multiple of 8
+/// and whose `refin` and `refout` are equal, the checksum is also appended to the message
+/// (in the order of processing), and the register is compared with the residue.
+///
+/// Meant as a startup health check, e. g. against memory corruption. Only available with the `alloc` feature.
+///
+/// ```
+/// assert_eq!(crc_rocksoft::catalog::self_test(), Ok(()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn self_test() -> Result<(), Vec<SelfTestFailure>> {
+    self_test_entries(all())
+}
+
+#[cfg(feature = "alloc")]
+fn self_test_entries<I: Iterator<Item = &'static CatalogEntry>>(entries: I) -> Result<(), Vec<SelfTestFailure>> {
+    let mut failures = Vec::new();
+    for entry in entries {
+        match entry.spec() {
+            CatalogSpec::U8(spec) => self_test_spec(entry, &spec, &mut failures),
+            CatalogSpec::U16(spec) => self_test_spec(entry, &spec, &mut failures),
+            CatalogSpec::U32(spec) => self_test_spec(entry, &spec, &mut failures),
+            CatalogSpec::U64(spec) => self_test_spec(entry, &spec, &mut failures),
+        }
+    }
+    if failures.is_empty() { Ok(()) } else { Err(failures) }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn self_test_spec<T: ValueType + Into<u64>>(entry: &'static CatalogEntry, spec: &CrcTable<T>, failures: &mut Vec<SelfTestFailure>) {
+    let message = b"123456789";
+    let value = message.iter().fold(spec.start(), |value, &byte| spec.update(value, byte));
+    let check = spec.finish(value).into();
+    if check != entry.check {
+        failures.push(SelfTestFailure { name: entry.name(), field: "check", expected: entry.check, actual: check });
+    }
+    if entry.width % 8 == 0 && entry.refin == entry.refout {
+        let trailer = if entry.refout { spec.finish_le_bytes(value) } else { spec.finish_be_bytes(value) };
+        let value = trailer.iter().fold(value, |value, &byte| spec.update(value, byte));
+        let residue = (spec.finish(value) ^ spec.xorout()).into();
+        let expected = spec.residue().into();
+        if residue != expected {
+            failures.push(SelfTestFailure { name: entry.name(), field: "residue", expected, actual: residue });
+        }
+    }
+}
+
+/// Parses an algorithm from a line of the RevEng catalogue, e. g.
+/// `width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0000 name="CRC-16/ARC"`.
+///
+/// The parameters are parsed like [`CrcTable::from_str`](../primitive/struct.CrcTable.html#impl-FromStr-for-CrcTable<T>)
+/// does, so the `check` and `residue` values are verified if present; `name` is required.
+/// Algorithms wider than 64 bits are rejected with `CrcError::UnsupportedWidth`.
+///
+/// Only available with the `alloc` feature.
+///
+/// ```
+/// use crc_rocksoft::catalog;
+///
+/// let line = r#"width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0000 name="CRC-16/ARC""#;
+/// let entry = catalog::parse_reveng_line(line).unwrap();
+/// assert_eq!(entry.name(), "CRC-16/ARC");
+/// assert_eq!(entry.check(), 0xBB3D);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn parse_reveng_line(line: &str) -> Result<CatalogEntry, CrcError<u64>> {
+    if let Some(width) = reveng_field(line, "width").and_then(|width| width.parse().ok()) {
+        if width > 64 {
+            return Err(CrcError::UnsupportedWidth(width));
+        }
+    }
+    let name = match reveng_field(line, "name") {
+        Some(name) if name.len() > 2 && name.starts_with('"') && name.ends_with('"') => &name[1..name.len() - 1],
+        Some(_) => return Err(CrcError::InvalidField("name")),
+        None => return Err(CrcError::MissingField("name")),
+    };
+    let spec: CrcTable<u64> = line.parse()?;
+    Ok(CatalogEntry {
+        names: Names::Owned(name.to_string()),
+        width: spec.width(),
+        poly: spec.poly(),
+        init: spec.init(),
+        refin: spec.refin(),
+        refout: spec.refout(),
+        xorout: spec.xorout(),
+        check: spec.check(),
+    })
+}
+
+#[cfg(feature = "alloc")]
+fn reveng_field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split_whitespace()
+        .filter_map(|param| param.find('=').map(|ix| (&param[..ix], &param[ix + 1..])))
+        .find(|&(k, _)| k == key)
+        .map(|(_, value)| value)
+}
+
+/// Parses every line of a file in the format of the RevEng catalogue with
+/// [`parse_reveng_line`](fn.parse_reveng_line.html). Empty lines and lines starting with `#` are skipped.
+///
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+pub fn load_reveng_file<R: io::BufRead>(reader: R) -> Result<Vec<CatalogEntry>, ParseError> {
+    let mut entries = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(ParseError::Io)?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = parse_reveng_line(line).map_err(|error| ParseError::Line { line: index + 1, error })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// The error type for [`load_reveng_file`](fn.load_reveng_file.html).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ParseError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// A line of the file couldn't be parsed.
+    Line {
+        /// The number of the line, starting from 1.
+        line: usize,
+        /// The reason, which names the offending parameter.
+        error: CrcError<u64>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Io(ref error) => write!(f, "can't read the catalogue: {}", error),
+            ParseError::Line { line, ref error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ParseError::Io(ref error) => Some(error),
+            ParseError::Line { ref error, .. } => Some(error),
+        }
+    }
+}
+
+fn same_parameters<A: ValueType, B: ValueType>(a: &impl CrcSpec<A>, b: &impl CrcSpec<B>) -> bool {
+    let width = a.width();
+    let same = |x: A, y: B| x.be_bytes(width).as_ref() == y.be_bytes(width).as_ref();
+    width == b.width() && a.refin() == b.refin() && a.refout() == b.refout()
+        && same(a.poly(), b.poly()) && same(a.init(), b.init()) && same(a.xorout(), b.xorout())
+}
+
+fn names_match(a: &str, b: &str) -> bool {
+    fn normalize(c: u8) -> u8 {
+        match c {
+            b'_' | b'/' => b'-',
+            c => c.to_ascii_uppercase(),
+        }
+    }
+    a.len() == b.len() && a.bytes().zip(b.bytes()).all(|(a, b)| normalize(a) == normalize(b))
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_macros)]
+    macro_rules! check_values {
+        ($($name:ident: $expected:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(super::$name().check(), $expected);
+                }
+            )*
+        }
+    }
+
+    #[cfg(feature = "catalog-crc8")]
+    check_values! {
+        crc3_gsm: 0x4,
+        crc3_rohc: 0x6,
+        crc4_g_704: 0x7,
+        crc4_interlaken: 0xB,
+        crc5_epc_c1g2: 0x00,
+        crc5_g_704: 0x07,
+        crc5_usb: 0x19,
+        crc6_cdma2000_a: 0x0D,
+        crc6_cdma2000_b: 0x3B,
+        crc6_darc: 0x26,
+        crc6_g_704: 0x06,
+        crc7_mmc: 0x75,
+        crc7_rohc: 0x53,
+        crc7_umts: 0x61,
+        crc8_autosar: 0xDF,
+        crc8_bluetooth: 0x26,
+        crc8_cdma2000: 0xDA,
+        crc8_darc: 0x15,
+        crc8_dvb_s2: 0xBC,
+        crc8_i_code: 0x7E,
+        crc8_itu: 0xA1,
+        crc8_maxim: 0xA1,
+        crc8_nrsc_5: 0xF7,
+        crc8_rohc: 0xD0,
+        crc8_smbus: 0xF4,
+    }
+
+    #[cfg(feature = "catalog-crc16")]
+    check_values! {
+        crc10_atm: 0x199,
+        crc10_cdma2000: 0x233,
+        crc10_gsm: 0x12A,
+        crc11_flexray: 0x5A3,
+        crc11_umts: 0x061,
+        crc12_cdma2000: 0xD4D,
+        crc12_dect: 0xF5B,
+        crc12_gsm: 0xB34,
+        crc12_umts: 0xDAF,
+        crc13_bbc: 0x04FA,
+        crc14_darc: 0x082D,
+        crc14_gsm: 0x30AE,
+        crc15_can: 0x059E,
+        crc15_mpt1327: 0x2566,
+        crc16_arc: 0xBB3D,
+        crc16_ccitt_false: 0x29B1,
+        crc16_dnp: 0xEA82,
+        crc16_genibus: 0xD64E,
+        crc16_ibm_sdlc: 0x906E,
+        crc16_kermit: 0x2189,
+        crc16_maxim: 0x44C2,
+        crc16_mcrf4xx: 0x6F91,
+        crc16_modbus: 0x4B37,
+        crc16_t10_dif: 0xD0DB,
+        crc16_usb: 0xB4C8,
+        crc16_x25: 0x906E,
+        crc16_xmodem: 0x31C3,
+    }
+
+    #[cfg(feature = "catalog-crc32")]
+    check_values! {
+        crc17_can_fd: 0x04F03,
+        crc21_can_fd: 0x0ED841,
+        crc24_ble: 0xC25A56,
+        crc24_flexray_a: 0x7979BD,
+        crc24_flexray_b: 0x1F23B8,
+        crc24_interlaken: 0xB4F3E6,
+        crc24_lte_a: 0xCDE703,
+        crc24_lte_b: 0x23EF52,
+        crc24_openpgp: 0x21CF02,
+        crc24_os_9: 0x200FA5,
+        crc32_autosar: 0x1697D06A,
+        crc32_bzip2: 0xFC891918,
+        crc32_iscsi: 0xE3069283,
+        crc32_iso_hdlc: 0xCBF43926,
+        crc32_jamcrc: 0x340BC6D9,
+        crc32_mpeg_2: 0x0376E6E7,
+        crc32_posix: 0x765E7680,
+        crc32_xfer: 0xBD0BE338,
+        crc32c: 0xE3069283,
+        crc32d: 0x87315576,
+        crc32q: 0x3010BF7F,
+    }
+
+    #[cfg(feature = "catalog-crc64")]
+    check_values! {
+        crc64_ecma_182: 0x6C40DF5F0B497347,
+        crc64_ms: 0x75D4B74F024ECEEA,
+        crc64_redis: 0xE9C6D914C4B8D9CA,
+        crc64_we: 0x62EC59E3F1A4F00A,
+        crc64_xz: 0x995DC9BBDF1939FA,
+    }
+
+    /// Widths between 8 and 16 mask the register in the non-reflected layout
+    /// and don't in the reflected one, so each width is checked both ways.
+    #[cfg(feature = "catalog-crc16")]
+    #[test]
+    fn crc10_to_crc15_match_reference() {
+        use std::vec::Vec;
+        use primitive::reference::reference_crc;
+        use primitive::CrcTable;
+        use ::CrcSpec;
+        let data: Vec<u8> = (0..=255).collect();
+        let specs = [super::crc10_atm(), super::crc11_flexray(), super::crc12_umts(), super::crc13_bbc(), super::crc14_darc(), super::crc15_can()];
+        for spec in &specs {
+            for &refin in &[false, true] {
+                let spec = CrcTable::new_with_width(spec.width(), spec.poly(), spec.init(), refin, spec.refout(), spec.xorout());
+                let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+                assert_eq!(spec.finish(value), reference_crc(&spec, &data), "{:?}", spec);
+            }
+        }
+    }
+
+    /// Regression tests for the non-reflected 16-bit path, which handles the register
+    /// differently from the reflected one, over a message longer than the check input.
+    #[cfg(feature = "catalog-crc16")]
+    #[test]
+    fn crc16_non_reflected_match_reference() {
+        use std::vec::Vec;
+        use primitive::reference::reference_crc;
+        use ::CrcSpec;
+        let data: Vec<u8> = (0..=255).collect();
+        for spec in &[super::crc16_ccitt_false(), super::crc16_genibus(), super::crc16_t10_dif(), super::crc16_xmodem()] {
+            assert!(!spec.refin());
+            let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+            assert_eq!(spec.finish(value), reference_crc(spec, &data), "{:?}", spec);
+        }
+    }
+
+    /// CRC-16/XMODEM and CRC-16/KERMIT are often mixed up, since they share the poly (0x1021),
+    /// `init` and `xorout` (both zero):
+    ///
+    /// | algorithm      | refin | refout | check  |
+    /// |----------------|-------|--------|--------|
+    /// | CRC-16/XMODEM  | false | false  | 0x31C3 |
+    /// | CRC-16/KERMIT  | true  | true   | 0x2189 |
+    ///
+    /// Reflecting only the output of XMODEM, or byte-swapping it, doesn't give KERMIT.
+    #[cfg(feature = "catalog-crc16")]
+    #[test]
+    fn crc16_xmodem_vs_kermit() {
+        use ::CrcSpec;
+        let (xmodem, kermit) = (super::crc16_xmodem(), super::crc16_kermit());
+        assert_eq!((xmodem.poly(), xmodem.init(), xmodem.xorout()), (0x1021, 0, 0));
+        assert_eq!((kermit.poly(), kermit.init(), kermit.xorout()), (0x1021, 0, 0));
+        assert_eq!((xmodem.refin(), xmodem.refout()), (false, false));
+        assert_eq!((kermit.refin(), kermit.refout()), (true, true));
+        assert_eq!(xmodem.check(), 0x31C3);
+        assert_eq!(kermit.check(), 0x2189);
+        assert_ne!(xmodem.clone().with_refout(true).check(), kermit.check());
+        assert_ne!(xmodem.check().swap_bytes(), kermit.check());
+        assert!(xmodem.with_refin(true).with_refout(true) == kermit);
+    }
+
+    /// The sub-byte widths shift the whole register out with each byte,
+    /// so they take a separate path in both table layouts.
+    #[cfg(feature = "catalog-crc8")]
+    #[test]
+    fn sub_byte_match_reference() {
+        use std::vec::Vec;
+        use primitive::reference::reference_crc;
+        let data: Vec<u8> = (0..=255).collect();
+        for spec in &[super::crc3_gsm(), super::crc3_rohc(), super::crc5_usb(), super::crc6_cdma2000_a(), super::crc7_mmc(), super::crc7_rohc()] {
+            let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+            assert_eq!(spec.finish(value), reference_crc(spec, &data), "{:?}", spec);
+        }
+    }
+
+    /// Coverage of the 8-bit path, both reflected and non-reflected,
+    /// over a message longer than the check input.
+    #[cfg(feature = "catalog-crc8")]
+    #[test]
+    fn crc8_match_reference() {
+        use std::vec::Vec;
+        use primitive::reference::reference_crc;
+        let data: Vec<u8> = (0..=255).collect();
+        for spec in &[super::crc8_autosar(), super::crc8_bluetooth(), super::crc8_maxim(), super::crc8_rohc(), super::crc8_smbus()] {
+            let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+            assert_eq!(spec.finish(value), reference_crc(spec, &data), "{:?}", spec);
+        }
+    }
+
+    #[cfg(feature = "catalog-crc64")]
+    #[test]
+    fn crc64_match_reference() {
+        use std::vec::Vec;
+        use primitive::reference::reference_crc;
+        let data: Vec<u8> = (0..=255).collect();
+        for spec in &[super::crc64_ecma_182(), super::crc64_we(), super::crc64_xz()] {
+            let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+            assert_eq!(spec.finish(value), reference_crc(spec, &data), "{:?}", spec);
+        }
+    }
+
+    /// CAN FD checksums cover a bit stream whose length is usually not a multiple of 8,
+    /// so the frame is processed as whole bytes followed by the remaining bits.
+    ///
+    /// The expected values don't come from the crate: they're the check values
+    /// published in the RevEng catalogue, fed as odd-sized groups of bits, and the remainder
+    /// of a plain polynomial long division over GF(2), which is what ISO 11898-1 specifies
+    /// for the CRC field (both algorithms have a zero `init` and `xorout`). A receiver
+    /// dividing the frame together with its CRC field gets a zero remainder.
+    #[cfg(feature = "catalog-crc32")]
+    #[test]
+    fn can_fd_match_reference() {
+        use std::vec::Vec;
+        use primitive::CrcTable;
+        use ::CrcSpec;
+
+        fn to_bits(bytes: &[u8]) -> Vec<bool> {
+            bytes.iter().flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1)).collect()
+        }
+
+        /// The remainder of the bits, followed by `width` zeros, divided by the generator.
+        fn divide(bits: &[bool], poly: u32, width: usize) -> u32 {
+            let generator = (1u64 << width) | poly as u64;
+            let remainder = bits.iter().chain(&[false; 32][..width])
+                .fold(0u64, |remainder, &bit| {
+                    let remainder = remainder << 1 | bit as u64;
+                    if remainder >> width & 1 == 1 { remainder ^ generator } else { remainder }
+                });
+            remainder as u32
+        }
+
+        fn crc_of_bits(spec: &CrcTable<u32>, bits: &[bool], group: usize) -> u32 {
+            let value = bits.chunks(group).fold(spec.start(), |value, chunk| {
+                let byte = chunk.iter().fold(0u8, |byte, &bit| byte << 1 | bit as u8);
+                spec.update_bits(value, byte, chunk.len())
+            });
+            spec.finish(value)
+        }
+
+        // Not a captured frame: an arbitrary 30-bit header, as long as the stuff count, SOF,
+        // 11-bit ID and control bits, followed by an arbitrary 64-byte payload.
+        let mut frame: Vec<bool> = (0..30).map(|i| (0x2A5F_0C93u32 >> i) & 1 == 1).collect();
+        frame.extend((0..64 * 8).map(|i| (i * 37 + i / 5) % 3 == 0));
+        for &(ref spec, check) in &[(super::crc17_can_fd(), 0x04F03), (super::crc21_can_fd(), 0x0ED841)] {
+            let width = spec.width();
+            for &group in &[3, 5, 7, 8] {
+                assert_eq!(crc_of_bits(spec, &to_bits(b"123456789"), group), check, "{:?}", spec);
+                assert_eq!(divide(&to_bits(b"123456789"), spec.poly(), width), check, "{:?}", spec);
+
+                let crc = crc_of_bits(spec, &frame, group);
+                assert_eq!(crc, divide(&frame, spec.poly(), width), "{:?}", spec);
+                let mut received = frame.clone();
+                received.extend((0..width).rev().map(|i| (crc >> i) & 1 == 1));
+                assert_eq!(crc_of_bits(spec, &received, group), 0, "{:?}", spec);
+
+                // The ISO 11898-1 variant: the top bit of the register set at the start
+                // is the same as inverting the first bit of the message.
+                let iso = CrcTable::new_with_width(width, spec.poly(), 1 << (width - 1), false, false, 0);
+                let mut inverted = frame.clone();
+                inverted[0] = !inverted[0];
+                let crc = crc_of_bits(&iso, &frame, group);
+                assert_eq!(crc, divide(&inverted, spec.poly(), width), "{:?}", iso);
+                let mut received = frame.clone();
+                received.extend((0..width).rev().map(|i| (crc >> i) & 1 == 1));
+                assert_eq!(crc_of_bits(&iso, &received, group), 0, "{:?}", iso);
+            }
+        }
+    }
+
+    /// Checksums of 24-bit algorithms backed by `u32` must not have the top byte set.
+    #[cfg(feature = "catalog-crc32")]
+    #[test]
+    fn crc24_fit_in_24_bits() {
+        use ::CrcHasher;
+        use primitive::CrcTableHasher;
+        let data: std::vec::Vec<u8> = (0..=255).collect();
+        for spec in &[super::crc24_ble(), super::crc24_interlaken(), super::crc24_openpgp(), super::crc24_os_9()] {
+            let mut hasher = CrcTableHasher::from(spec);
+            for chunk in data.chunks(7) {
+                hasher.update_from_slice(chunk);
+                assert_eq!(hasher.finish() & !0xFFFFFF, 0, "{:?}", spec);
+            }
+        }
+    }
+
+    #[test]
+    fn from_name_all_names() {
+        for entry in super::all() {
+            for name in ::core::iter::once(&entry.name()).chain(entry.aliases()) {
+                let found = super::from_name(name).unwrap();
+                assert!(::core::ptr::eq(found, entry), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn all_sorted_by_name() {
+        let names: std::vec::Vec<&str> = super::all().map(|entry| entry.name()).collect();
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", names);
+        #[cfg(feature = "catalog-full")]
+        assert_eq!(names.len(), 76);
+    }
+
+    #[test]
+    fn entries_match_check_values() {
+        use ::CrcHasher;
+        for entry in super::all() {
+            let mut hasher = entry.hasher();
+            hasher.update_from_slice(b"123456789");
+            assert_eq!(hasher.finish(), entry.check(), "{}", entry.name());
+        }
+    }
+
+    #[cfg(feature = "catalog-full")]
+    #[test]
+    fn entries_match_constructors() {
+        use super::CatalogSpec;
+        match super::from_name("CRC-32/ISO-HDLC").unwrap().spec() {
+            CatalogSpec::U32(spec) => assert!(spec == super::crc32_iso_hdlc()),
+            spec => panic!("{:?}", spec),
+        }
+        match super::from_name("CRC-5/USB").unwrap().spec() {
+            CatalogSpec::U8(spec) => assert!(spec == super::crc5_usb()),
+            spec => panic!("{:?}", spec),
+        }
+        match super::from_name("CRC-12/UMTS").unwrap().spec() {
+            CatalogSpec::U16(spec) => assert!(spec == super::crc12_umts()),
+            spec => panic!("{:?}", spec),
+        }
+        match super::from_name("CRC-64/XZ").unwrap().spec() {
+            CatalogSpec::U64(spec) => assert!(spec == super::crc64_xz()),
+            spec => panic!("{:?}", spec),
+        }
+    }
+
+    #[cfg(feature = "catalog-full")]
+    #[test]
+    fn entry_residues() {
+        let residue = |name| super::from_name(name).unwrap().residue();
+        assert_eq!(residue("CRC-32/ISO-HDLC"), 0xDEBB20E3);
+        assert_eq!(residue("CRC-32/BZIP2"), 0xC704DD7B);
+        assert_eq!(residue("CRC-16/GENIBUS"), 0x1D0F);
+        assert_eq!(residue("CRC-16/MODBUS"), 0x0000);
+        assert_eq!(residue("CRC-15/MPT1327"), 0x6815);
+    }
+
+    #[cfg(feature = "catalog-full")]
+    #[test]
+    fn from_name_aliases() {
+        use ::CrcHasher;
+        let check = |name: &str| {
+            let entry = super::from_name(name).unwrap();
+            let mut hasher = entry.hasher();
+            hasher.update_from_slice(b"123456789");
+            hasher.finish()
+        };
+        assert_eq!(check("CRC-32"), 0xCBF43926);
+        assert_eq!(check("CRC32"), 0xCBF43926);
+        assert_eq!(check("CRC32C"), 0xE3069283);
+        assert_eq!(check("CRC-32C"), 0xE3069283);
+        assert_eq!(check("CKSUM"), 0x765E7680);
+        assert_eq!(check("XMODEM"), 0x31C3);
+        assert_eq!(check("MODBUS"), 0x4B37);
+        assert_eq!(check("JAMCRC"), 0x340BC6D9);
+        assert_eq!(check("CRC-8/MAXIM"), 0xA1);
+        assert_eq!(check("CRC-64/XZ"), 0x995DC9BBDF1939FA);
+    }
+
+    /// As in the RevEng catalogue, a name without a family refers to one particular algorithm.
+    #[cfg(feature = "catalog-crc16")]
+    #[test]
+    fn from_name_family_only() {
+        use ::CrcHasher;
+        let entry = super::from_name("CRC-16").unwrap();
+        let mut hasher = entry.hasher();
+        hasher.update_from_slice(b"123456789");
+        assert_eq!(hasher.finish(), u64::from(super::crc16_arc().check()));
+        assert!(super::from_name("CRC-16/").is_none());
+    }
+
+    #[cfg(feature = "catalog-crc32")]
+    #[test]
+    fn from_name_normalization() {
+        for name in &["crc-32/bzip2", "CRC_32_BZIP2", "Crc-32-Bzip2", "crc/32/bzip2"] {
+            assert_eq!(super::from_name(name).map(|e| e.width()), Some(32), "{}", name);
+        }
+        assert!(super::from_name("CRC 32/BZIP2").is_none());
+        assert!(super::from_name("CRC-32/BZIP").is_none());
+    }
+
+    /// Names of algorithms whose family is compiled out are unknown.
+    #[test]
+    fn from_name_families() {
+        let families = [
+            ("CRC-8/SMBUS", cfg!(feature = "catalog-crc8")),
+            ("CRC-5/USB", cfg!(feature = "catalog-crc8")),
+            ("CRC-16/ARC", cfg!(feature = "catalog-crc16")),
+            ("CRC-15/CAN", cfg!(feature = "catalog-crc16")),
+            ("CRC-32/ISO-HDLC", cfg!(feature = "catalog-crc32")),
+            ("CRC-24/OPENPGP", cfg!(feature = "catalog-crc32")),
+            ("CRC-64/XZ", cfg!(feature = "catalog-crc64")),
+        ];
+        for &(name, enabled) in &families {
+            assert_eq!(super::from_name(name).is_some(), enabled, "{}", name);
+        }
+    }
+
+    #[test]
+    fn from_name_unknown() {
+        for name in &["", "CRC", "CRC-32/BZIP2 ", "CRC-99/UNKNOWN", "\u{1F600}"] {
+            assert!(super::from_name(name).is_none(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let all: std::vec::Vec<&str> = super::all()
+            .flat_map(|entry| ::core::iter::once(entry.name()).chain(entry.aliases().iter().cloned()))
+            .collect();
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                assert!(!super::names_match(a, b), "{} and {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn identify_exact() {
+        use super::CatalogSpec;
+        for entry in super::all() {
+            let name = match entry.spec() {
+                CatalogSpec::U8(ref spec) => super::identify(spec),
+                CatalogSpec::U16(ref spec) => super::identify(spec),
+                CatalogSpec::U32(ref spec) => super::identify(spec),
+                CatalogSpec::U64(ref spec) => super::identify(spec),
+            };
+            assert_eq!(name, Some(entry.name()));
+            assert_eq!(super::identify(entry), Some(entry.name()));
+        }
+        #[cfg(feature = "catalog-crc32")]
+        assert_eq!(super::identify(&super::crc32c()), Some("CRC-32/ISCSI"));
+        #[cfg(feature = "catalog-crc16")]
+        assert_eq!(super::identify(&super::crc16_x25()), Some("CRC-16/IBM-SDLC"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn identify_by_check() {
+        use super::{all, identify_by_check};
+        for entry in all() {
+            assert!(identify_by_check(entry.width(), entry.check()).contains(&entry.name()), "{}", entry.name());
+            assert!(!identify_by_check(entry.width() + 1, entry.check()).contains(&entry.name()), "{}", entry.name());
+        }
+        assert!(identify_by_check(32, 0xDEADBEEF).is_empty());
+    }
+
+    #[cfg(feature = "catalog-crc32")]
+    #[test]
+    fn identify_by_check_known() {
+        use super::identify_by_check;
+        assert_eq!(identify_by_check(32, 0xCBF43926), ["CRC-32/ISO-HDLC"]);
+        assert_eq!(identify_by_check(32, 0xE3069283), ["CRC-32/ISCSI"]);
+        assert!(identify_by_check(24, 0xCBF43926).is_empty());
+    }
+
+    #[test]
+    fn identify_near_misses() {
+        use primitive::CrcTable;
+        assert_eq!(super::identify(&CrcTable::new(0x04C11DB7u32, 0xFFFFFFFEu32, true, true, 0xFFFFFFFFu32)), None);
+        assert_eq!(super::identify(&CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0xFFFFFFFFu32)), None);
+        assert_eq!(super::identify(&CrcTable::new(0x1021u16, 0x0001u16, false, false, 0u16)), None);
+        assert_eq!(super::identify(&CrcTable::new_with_width(23, 0x064CFBu32, 0x3704CEu32, false, false, 0u32)), None);
+    }
+
+    /// A spec backed by a wider type is the same algorithm if its width is the same.
+    #[cfg(feature = "catalog-full")]
+    #[test]
+    fn identify_wider_register() {
+        use primitive::{CrcTable, Wide};
+        let crc32 = CrcTable::new_with_width(32, 0x04C11DB7u64, 0xFFFFFFFFu64, true, true, 0xFFFFFFFFu64);
+        assert_eq!(super::identify(&crc32), Some("CRC-32/ISO-HDLC"));
+        let crc24 = CrcTable::new_with_width(24, 0x864CFBu128, 0xB704CEu128, false, false, 0u128);
+        assert_eq!(super::identify(&crc24), Some("CRC-24/OPENPGP"));
+        let crc16 = CrcTable::new_with_width(16, Wide([0, 0, 0x80, 0x05]), Wide([0; 4]), true, true, Wide([0; 4]));
+        assert_eq!(super::identify(&crc16), Some("CRC-16/ARC"));
+        // The same values in a spec of a different width are a different algorithm.
+        let crc33 = CrcTable::new_with_width(33, 0x04C11DB7u64, 0xFFFFFFFFu64, true, true, 0xFFFFFFFFu64);
+        assert_eq!(super::identify(&crc33), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn self_test() {
+        assert_eq!(super::self_test(), Ok(()));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn self_test_reports_failures() {
+        use super::{CatalogEntry, Names, SelfTestFailure};
+        static ENTRIES: [CatalogEntry; 3] = [
+            CatalogEntry { names: Names::Static(&["CRC-16/ARC"]), width: 16, poly: 0x8005, init: 0, refin: true, refout: true, xorout: 0, check: 0xBB3D },
+            CatalogEntry { names: Names::Static(&["CRC-16/BAD"]), width: 16, poly: 0x8005, init: 0, refin: true, refout: true, xorout: 0, check: 0xBB3E },
+            CatalogEntry { names: Names::Static(&["CRC-5/BAD"]), width: 5, poly: 0x05, init: 0x1F, refin: true, refout: true, xorout: 0x1F, check: 0x18 },
+        ];
+        assert_eq!(super::self_test_entries(ENTRIES.iter()), Err(vec![
+            SelfTestFailure { name: "CRC-16/BAD", field: "check", expected: 0xBB3E, actual: 0xBB3D },
+            SelfTestFailure { name: "CRC-5/BAD", field: "check", expected: 0x18, actual: 0x19 },
+        ]));
+    }
+
+    #[cfg(feature = "alloc")]
+    mod dyn_hasher {
+        use super::super::{all, dyn_hasher};
+        use primitive::ValueType;
+
+        #[test]
+        fn check_values() {
+            for entry in all() {
+                let mut hasher = dyn_hasher(entry.name()).unwrap();
+                hasher.update_from_slice(b"123456789");
+                assert_eq!(hasher.width(), entry.width(), "{}", entry.name());
+                assert_eq!(hasher.finish_u64(), entry.check(), "{}", entry.name());
+                assert_eq!(hasher.finish_bytes(), &*entry.check().be_bytes(entry.width()), "{}", entry.name());
+            }
+        }
+
+        #[cfg(feature = "catalog-crc32")]
+        #[test]
+        fn reset() {
+            let mut hasher = dyn_hasher("CRC-32/ISO-HDLC").unwrap();
+            hasher.update_from_slice(b"garbage");
+            hasher.reset();
+            for &b in b"123456789" {
+                hasher.update(b);
+            }
+            assert_eq!(hasher.finish_u64(), 0xCBF43926);
+        }
+
+        #[cfg(feature = "catalog-full")]
+        #[test]
+        fn every_backing_type() {
+            let hashers: [(&str, &[u8]); 4] = [
+                ("CRC-8/SMBUS", &[0xF4]),
+                ("CRC-12/UMTS", &[0x0D, 0xAF]),
+                ("CRC-24/OPENPGP", &[0x21, 0xCF, 0x02]),
+                ("CRC-64/XZ", &[0x99, 0x5D, 0xC9, 0xBB, 0xDF, 0x19, 0x39, 0xFA]),
+            ];
+            for &(name, check) in &hashers {
+                let mut hasher = dyn_hasher(name).unwrap();
+                hasher.update_from_slice(b"123456789");
+                assert_eq!(hasher.finish_bytes(), check, "{}", name);
+            }
+        }
+
+        #[test]
+        fn unknown() {
+            assert!(dyn_hasher("CRC-32/UNKNOWN").is_none());
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    mod reveng {
+        use ::CrcHasher;
+        use std::string::ToString;
+        use primitive::CrcError;
+        use super::super::parse_reveng_line;
+
+        const ARC: &str = "width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0000 name=\"CRC-16/ARC\"";
+
+        #[test]
+        fn parse_line() {
+            let entry = parse_reveng_line(ARC).unwrap();
+            assert_eq!(entry.name(), "CRC-16/ARC");
+            assert!(entry.aliases().is_empty());
+            assert_eq!((entry.width(), entry.check(), entry.residue()), (16, 0xBB3D, 0));
+            let mut hasher = entry.hasher();
+            hasher.update_from_slice(b"123456789");
+            assert_eq!(hasher.finish(), 0xBB3D);
+            #[cfg(feature = "catalog-crc16")]
+            assert_eq!(super::super::identify(&entry), Some("CRC-16/ARC"));
+        }
+
+        #[test]
+        fn without_check() {
+            let entry = parse_reveng_line("width=24 poly=0x864cfb init=0xb704ce refin=false refout=false xorout=0 name=\"CRC-24/X\"").unwrap();
+            assert_eq!(entry.check(), 0x21CF02);
+        }
+
+        #[test]
+        fn errors() {
+            assert_eq!(parse_reveng_line(&ARC.replace("check=0xbb3d", "check=0xbb3e")).err(),
+                       Some(CrcError::Mismatch { field: "check", expected: 0xBB3E, actual: 0xBB3D }));
+            assert_eq!(parse_reveng_line(&ARC.replace(" name=\"CRC-16/ARC\"", "")).err(), Some(CrcError::MissingField("name")));
+            assert_eq!(parse_reveng_line(&ARC.replace("\"CRC-16/ARC\"", "CRC-16/ARC")).err(), Some(CrcError::InvalidField("name")));
+            assert_eq!(parse_reveng_line(&ARC.replace("init=0x0000 ", "")).err(), Some(CrcError::MissingField("init")));
+            assert_eq!(parse_reveng_line(&ARC.replace("refin=true", "refin=yes")).err(), Some(CrcError::InvalidField("refin")));
+        }
+
+        #[test]
+        fn display_round_trip() {
+            assert_eq!(parse_reveng_line(ARC).unwrap().to_string(), ARC);
+            for entry in super::super::all() {
+                let line = entry.to_string();
+                let parsed = parse_reveng_line(&line).unwrap();
+                assert_eq!(parsed.name(), entry.name());
+                assert_eq!(parsed.check(), entry.check(), "{}", line);
+                assert_eq!(parsed.to_string(), line);
+            }
+        }
+
+        #[test]
+        fn too_wide() {
+            let line = "width=82 poly=0x0308c0111011401440411 init=0x000000000000000000000 refin=true refout=true \
+                        xorout=0x000000000000000000000 check=0x09ea83f625023801fd612 name=\"CRC-82/DARC\"";
+            assert_eq!(parse_reveng_line(line).err(), Some(CrcError::UnsupportedWidth(82)));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod load_reveng_file {
+        use std::io::{self, Read, BufReader};
+        use std::string::ToString;
+        use primitive::CrcError;
+        use super::super::{load_reveng_file, ParseError};
+
+        #[test]
+        fn file() {
+            let file = "# A comment\n\
+                width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0000 name=\"CRC-16/ARC\"\n\
+                \n\
+                width=32 poly=0x04c11db7 init=0xffffffff refin=false refout=false xorout=0xffffffff check=0xfc891918 residue=0xc704dd7b name=\"CRC-32/BZIP2\"\n";
+            let entries = load_reveng_file(file.as_bytes()).unwrap();
+            assert_eq!(entries.iter().map(|entry| entry.name()).collect::<std::vec::Vec<_>>(), ["CRC-16/ARC", "CRC-32/BZIP2"]);
+        }
+
+        #[test]
+        fn line_number() {
+            let file = "# A comment\n\
+                width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d name=\"CRC-16/ARC\"\n\
+                \n\
+                width=16 poly=0x8005 init=0x0000 refin=true refout=true xorout=0x0000 check=0xbb3d residue=0x0001 name=\"CRC-16/BAD\"\n";
+            match load_reveng_file(file.as_bytes()) {
+                Err(ParseError::Line { line, error }) => {
+                    assert_eq!(line, 4);
+                    assert_eq!(error, CrcError::Mismatch { field: "residue", expected: 1, actual: 0 });
+                }
+                other => panic!("{:?}", other),
+            }
+            let error = load_reveng_file(file.as_bytes()).unwrap_err();
+            assert_eq!(error.to_string(), "line 4: the listed residue (1) differs from the computed one (0)");
+        }
+
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+
+        #[test]
+        fn io_error() {
+            match load_reveng_file(BufReader::new(Broken)) {
+                Err(ParseError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::Other),
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
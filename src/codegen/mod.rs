@@ -0,0 +1,356 @@
+//! Generation of Rust and C source code for specs with precomputed lookup tables.
+//! Only available with the `codegen` feature.
+//!
+//! # Rust
+//!
+//! This is meant for build scripts of `no_std` crates that need a table in a `static`,
+//! but can't use `CrcTable::new_const`, e. g. because the parameters are only known
+//! when building, or because computing the table at compile time is too slow.
+//! The build script writes the generated source into `OUT_DIR`:
+//!
+//! ```no_run
+//! # extern crate crc_rocksoft;
+//! use std::{env, fs, path::Path};
+//! use crc_rocksoft::codegen::generate_rust;
+//! use crc_rocksoft::primitive::CrcParams;
+//!
+//! fn main() {
+//!     let params = CrcParams { width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF };
+//!     let path = Path::new(&env::var("OUT_DIR").unwrap()).join("crc32.rs");
+//!     fs::write(path, generate_rust(&params, "CRC32")).unwrap();
+//! }
+//! ```
+//!
+//! and the crate includes it wherever `CrcTable` and `CrcParams` are in scope:
+//!
+//! ```ignore
+//! use crc_rocksoft::primitive::{CrcTable, CrcParams};
+//!
+//! include!(concat!(env!("OUT_DIR"), "/crc32.rs"));
+//!
+//! assert_eq!(CRC32.check(), 0xCBF43926);
+//! ```
+//!
+//! # C
+//!
+//! [`generate_c`](fn.generate_c.html) writes the lookup table as a C array, so that firmware
+//! in C uses exactly the same table as the Rust code, and
+//! [`generate_c_header`](fn.generate_c_header.html) writes the declaration for it.
+
+use std::string::String;
+use std::fmt::Write;
+use core::convert::TryFrom;
+use primitive::{ValueType, CrcParams, CrcTable, Hex};
+
+
+/// Returns the source of a `pub static` item named `ident`, which holds a `CrcTable<u32>`
+/// constructed with [`CrcTable::from_parts_const`](../primitive/struct.CrcTable.html#method.from_parts_const)
+/// from `spec` and the fully expanded lookup table. The item refers to `CrcTable` and `CrcParams`
+/// unqualified, so they have to be imported where the source is included.
+///
+/// The output only depends on the arguments, so regenerating it doesn't make
+/// the build script's output look changed to version control or to `cargo`.
+///
+/// ```
+/// use crc_rocksoft::codegen::generate_rust;
+/// use crc_rocksoft::primitive::CrcParams;
+///
+/// let params = CrcParams { width: 8, poly: 0x07, init: 0, refin: false, refout: false, xorout: 0 };
+/// let source = generate_rust(&params, "CRC8");
+/// assert!(source.contains("pub static CRC8: CrcTable<u32> = CrcTable::<u32>::from_parts_const("));
+/// assert!(source.contains("0x00, 0x07, 0x0e, 0x09, 0x1c, 0x1b, 0x12, 0x15,"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the parameters are invalid (see
+/// [`CrcTable::try_new`](../primitive/struct.CrcTable.html#method.try_new)),
+/// or if `ident` is not a Rust identifier.
+pub fn generate_rust(spec: &CrcParams<u32>, ident: &str) -> String {
+    assert!(is_identifier(ident), "not a Rust identifier: {:?}", ident);
+    let table = CrcTable::try_from(*spec).unwrap_or_else(|e| panic!("{}", e));
+    let digits = spec.width.div_ceil(4) + 2;
+    let mut out = String::new();
+    // Writing into a String never fails.
+    writeln!(out, "// Generated by crc_rocksoft::codegen::generate_rust.").unwrap();
+    writeln!(out, "// {}", table).unwrap();
+    writeln!(out, "pub static {}: CrcTable<u32> = CrcTable::<u32>::from_parts_const(", ident).unwrap();
+    writeln!(
+        out,
+        "    CrcParams {{ width: {}, poly: {:#0d$x}, init: {:#0d$x}, refin: {}, refout: {}, xorout: {:#0d$x} }},",
+        spec.width, spec.poly, spec.init, spec.refin, spec.refout, spec.xorout, d = digits
+    ).unwrap();
+    writeln!(out, "    [").unwrap();
+    for row in table.table().chunks(8) {
+        out.push_str("       ");
+        for entry in row {
+            write!(out, " {:#0d$x},", entry, d = digits).unwrap();
+        }
+        out.push('\n');
+    }
+    writeln!(out, "    ],").unwrap();
+    writeln!(out, ");").unwrap();
+    out
+}
+
+/// Options for [`generate_c`](fn.generate_c.html) and
+/// [`generate_c_header`](fn.generate_c_header.html).
+///
+/// ```
+/// use crc_rocksoft::codegen::COptions;
+///
+/// let options = COptions { attribute: Some("PROGMEM"), ..COptions::default() };
+/// assert_eq!(options.row_width, 8);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct COptions<'a> {
+    /// The number of table entries per line. 8 by default.
+    pub row_width: usize,
+    /// Placed after the declarator, e. g. `PROGMEM` for AVR, or
+    /// `__attribute__((section(".crc_table")))`. `None` by default.
+    pub attribute: Option<&'a str>,
+    /// Whether the table has external linkage, to be declared in a header
+    /// generated by `generate_c_header`. Otherwise, the table is `static`. `false` by default.
+    pub external: bool,
+}
+
+impl<'a> Default for COptions<'a> {
+    fn default() -> Self {
+        COptions { row_width: 8, attribute: None, external: false }
+    }
+}
+
+/// Returns the C source of a `const` array named `symbol` with the lookup table of `spec`,
+/// the same one that `CrcTable` computes. The type of the entries is the smallest
+/// of `uint8_t`, `uint16_t`, `uint32_t` and `uint64_t` that fits the width,
+/// and the source includes `stdint.h` for it.
+///
+/// Like [`generate_rust`](fn.generate_rust.html), the output only depends on the arguments.
+///
+/// ```
+/// use crc_rocksoft::codegen::{generate_c, COptions};
+/// use crc_rocksoft::primitive::CrcParams;
+///
+/// let params = CrcParams { width: 16, poly: 0x1021u16, init: 0, refin: false, refout: false, xorout: 0 };
+/// let options = COptions { row_width: 4, attribute: Some("PROGMEM"), ..COptions::default() };
+/// let source = generate_c(&params, "crc16_table", &options);
+/// assert!(source.contains("static const uint16_t crc16_table[256] PROGMEM = {\n    0x0000, 0x1021, 0x2042, 0x3063,\n"));
+/// assert!(source.ends_with("    0x6e17, 0x7e36, 0x4e55, 0x5e74,\n    0x2e93, 0x3eb2, 0x0ed1, 0x1ef0,\n};\n"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the parameters are invalid (see
+/// [`CrcTable::try_new`](../primitive/struct.CrcTable.html#method.try_new)),
+/// if the width is over 64 bits, if `symbol` is not a C identifier,
+/// or if `options.row_width` is zero.
+pub fn generate_c<T: ValueType>(spec: &CrcParams<T>, symbol: &str, options: &COptions) -> String {
+    assert!(options.row_width > 0, "the row width must not be zero");
+    let table = CrcTable::try_from(*spec).unwrap_or_else(|e| panic!("{}", e));
+    let mut out = String::new();
+    write_c_preamble(&mut out, &table, symbol, "generate_c");
+    writeln!(out, "{} = {{", c_declarator(spec.width, symbol, options)).unwrap();
+    for row in table.table().chunks(options.row_width) {
+        out.push_str("   ");
+        for &entry in row {
+            write!(out, " {},", Hex(entry, spec.width)).unwrap();
+        }
+        out.push('\n');
+    }
+    writeln!(out, "}};").unwrap();
+    out
+}
+
+/// Returns a C header with the `extern` declaration of the table generated by
+/// [`generate_c`](fn.generate_c.html) with the same arguments, guarded against
+/// repeated inclusion by the macro `SYMBOL_H`, where `SYMBOL` is `symbol` in upper case.
+///
+/// ```
+/// use crc_rocksoft::codegen::{generate_c_header, COptions};
+/// use crc_rocksoft::primitive::CrcParams;
+///
+/// let params = CrcParams { width: 32, poly: 0x04C11DB7u32, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF };
+/// let options = COptions { external: true, ..COptions::default() };
+/// let header = generate_c_header(&params, "crc32_table", &options);
+/// assert!(header.starts_with("#ifndef CRC32_TABLE_H\n#define CRC32_TABLE_H\n"));
+/// assert!(header.contains("\nextern const uint32_t crc32_table[256];\n"));
+/// ```
+///
+/// # Panics
+///
+/// Panics in the same cases as `generate_c`, and if `options.external` is not set,
+/// since a `static` table can't be declared in a header.
+pub fn generate_c_header<T: ValueType>(spec: &CrcParams<T>, symbol: &str, options: &COptions) -> String {
+    assert!(options.external, "a static table can't be declared in a header");
+    let table = CrcTable::try_from(*spec).unwrap_or_else(|e| panic!("{}", e));
+    let guard = symbol.to_ascii_uppercase() + "_H";
+    let mut out = String::new();
+    writeln!(out, "#ifndef {}", guard).unwrap();
+    writeln!(out, "#define {}", guard).unwrap();
+    out.push('\n');
+    write_c_preamble(&mut out, &table, symbol, "generate_c_header");
+    writeln!(out, "extern {};", c_declarator(spec.width, symbol, options)).unwrap();
+    out.push('\n');
+    writeln!(out, "#endif").unwrap();
+    out
+}
+
+/// Writes the comments describing the table, and the `#include` for its type.
+fn write_c_preamble<T: ValueType>(out: &mut String, table: &CrcTable<T>, symbol: &str, generator: &str) {
+    assert!(is_identifier(symbol), "not a C identifier: {:?}", symbol);
+    writeln!(out, "/* Generated by crc_rocksoft::codegen::{}. */", generator).unwrap();
+    writeln!(out, "/* {} */", table).unwrap();
+    writeln!(out, "#include <stdint.h>").unwrap();
+    out.push('\n');
+}
+
+/// The declarator of the table without `extern` and without the initializer.
+fn c_declarator(width: usize, symbol: &str, options: &COptions) -> String {
+    let c_type = match width {
+        0..=8 => "uint8_t",
+        9..=16 => "uint16_t",
+        17..=32 => "uint32_t",
+        33..=64 => "uint64_t",
+        _ => panic!("C has no standard type for a width of {} bits", width)
+    };
+    let mut declarator = String::new();
+    if !options.external {
+        declarator.push_str("static ");
+    }
+    write!(declarator, "const {} {}[256]", c_type, symbol).unwrap();
+    if let Some(attribute) = options.attribute {
+        write!(declarator, " {}", attribute).unwrap();
+    }
+    declarator
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => s != "_" && chars.all(|c| c == '_' || c.is_ascii_alphanumeric()),
+        _ => false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use ::CrcSpec;
+    use primitive::{ValueType, CrcParams, CrcTable};
+    use super::{generate_rust, generate_c, generate_c_header, COptions};
+
+    const PARAMS: CrcParams<u32> = CrcParams {
+        width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF
+    };
+
+    // Regenerate with `generate_rust(&PARAMS, "CRC32")` if the format changes.
+    include!("testdata/crc32.rs");
+
+    #[test]
+    fn generated_source_compiles() {
+        assert_eq!(CRC32.check(), 0xCBF43926);
+        let expected = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+        assert!(CRC32 == expected);
+        assert_eq!(&CRC32.table()[..], &expected.table()[..]);
+    }
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate_rust(&PARAMS, "CRC32"), include_str!("testdata/crc32.rs"));
+        assert_eq!(generate_rust(&PARAMS, "CRC32"), generate_rust(&PARAMS, "CRC32"));
+    }
+
+    #[test]
+    fn narrow_width() {
+        let params = CrcParams { width: 5, poly: 0x05, init: 0x1F, refin: true, refout: true, xorout: 0x1F };
+        let source = generate_rust(&params, "CRC5_USB");
+        assert!(source.contains("CrcParams { width: 5, poly: 0x05, init: 0x1f, refin: true, refout: true, xorout: 0x1f },"));
+        let entries: Vec<_> = source.split('[').nth(1).unwrap().split(']').next().unwrap()
+            .split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+        assert_eq!(entries.len(), 256);
+        assert_eq!(&entries[..4], &["0x00", "0x0e", "0x1c", "0x12"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a Rust identifier")]
+    fn invalid_ident() {
+        generate_rust(&PARAMS, "CRC-32");
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit in the width")]
+    fn invalid_params() {
+        generate_rust(&CrcParams { width: 16, ..PARAMS }, "CRC16");
+    }
+
+    /// Parses the entries of a table generated by `generate_c` back into numbers.
+    fn parse_c(source: &str) -> Vec<u64> {
+        let start = source.find('{').unwrap() + 1;
+        let end = source.find('}').unwrap();
+        source[start..end].split(',').map(str::trim).filter(|s| !s.is_empty())
+            .map(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).unwrap())
+            .collect()
+    }
+
+    /// Returns `refin` of the spec, to count the algorithms of each kind.
+    fn assert_c_table<T: ValueType>(spec: &CrcTable<T>, c_type: &str) -> bool {
+        let params = spec.params();
+        let options = COptions { row_width: 5, ..COptions::default() };
+        let source = generate_c(&params, "table", &options);
+        assert!(source.contains(&format!("static const {} table[256] = {{", c_type)), "{}", source);
+        let expected: Vec<u64> = spec.table().iter()
+            .map(|v| v.le_bytes(spec.width()).iter().rev().fold(0, |r, &b| r << 8 | u64::from(b)))
+            .collect();
+        assert_eq!(parse_c(&source), expected, "{:?}", spec);
+        assert_eq!(source.lines().filter(|line| line.starts_with("    0x")).count(), 52);
+        spec.refin()
+    }
+
+    #[test]
+    fn c_catalog_entries() {
+        use catalog::{all, CatalogSpec};
+        let (mut reflected, mut non_reflected) = (0, 0);
+        for entry in all() {
+            let refin = match entry.spec() {
+                CatalogSpec::U8(spec) => assert_c_table(&spec, "uint8_t"),
+                CatalogSpec::U16(spec) => assert_c_table(&spec, if spec.width() > 8 { "uint16_t" } else { "uint8_t" }),
+                CatalogSpec::U32(spec) => assert_c_table(&spec, if spec.width() > 16 { "uint32_t" } else { "uint16_t" }),
+                CatalogSpec::U64(spec) => assert_c_table(&spec, if spec.width() > 32 { "uint64_t" } else { "uint32_t" }),
+            };
+            if refin { reflected += 1 } else { non_reflected += 1 }
+        }
+        if cfg!(feature = "catalog-full") {
+            assert!(reflected > 0 && non_reflected > 0);
+        }
+    }
+
+    #[test]
+    fn c_options() {
+        let params = CrcParams { width: 12, poly: 0x80Fu16, init: 0, refin: false, refout: true, xorout: 0 };
+        let source = generate_c(&params, "crc12", &COptions { row_width: 16, attribute: Some("PROGMEM"), external: true });
+        assert!(source.starts_with("/* Generated by crc_rocksoft::codegen::generate_c. */\n/* width=12 poly=0x80f "));
+        assert!(source.contains("\n#include <stdint.h>\n\nconst uint16_t crc12[256] PROGMEM = {\n    0x000, 0x80f, 0x811, 0x01e,"));
+        assert_eq!(source.lines().filter(|line| line.starts_with("    0x")).count(), 16);
+        assert_eq!(parse_c(&source).len(), 256);
+
+        let header = generate_c_header(&params, "crc12", &COptions { row_width: 16, attribute: Some("PROGMEM"), external: true });
+        assert_eq!(header, "#ifndef CRC12_H\n#define CRC12_H\n\n\
+            /* Generated by crc_rocksoft::codegen::generate_c_header. */\n\
+            /* width=12 poly=0x80f init=0x000 refin=false refout=true xorout=0x000 check=0xdaf residue=0x000 */\n\
+            #include <stdint.h>\n\n\
+            extern const uint16_t crc12[256] PROGMEM;\n\n#endif\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "a static table can't be declared in a header")]
+    fn c_header_of_static() {
+        generate_c_header(&PARAMS, "crc32", &COptions::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "C has no standard type for a width of 82 bits")]
+    fn c_too_wide() {
+        let params = CrcParams { width: 82, poly: 0x0308C0111011401440411u128, init: 0, refin: true, refout: true, xorout: 0 };
+        generate_c(&params, "crc82", &COptions::default());
+    }
+}
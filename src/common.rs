@@ -0,0 +1,123 @@
+//! Ready-to-use types for the most widely used algorithms, for when the algorithm
+//! is known in advance and its name is all that matters.
+//!
+//! Each type wraps the `CrcTable` of an algorithm from the [`catalog`](../catalog/index.html),
+//! which its `Default` implementation builds. It dereferences to the table, and it can
+//! be turned into a hasher like the table itself. It's available with the catalog
+//! feature of its family; the module is absent if no family is enabled.
+//!
+//! ```
+//! use crc_rocksoft::*;
+//! use crc_rocksoft::common::Crc32;
+//! use crc_rocksoft::primitive::CrcTableHasher;
+//!
+//! let mut hasher = CrcTableHasher::from(Crc32::default());
+//! hasher.update_from_slice(b"123456789");
+//! assert_eq!(hasher.finish(), 0xCBF43926);
+//! ```
+
+use ::CrcSpec;
+use primitive::CrcTable;
+use core::borrow::Borrow;
+use core::ops::Deref;
+
+
+macro_rules! common {
+    ($($(#[$attr:meta])* $name:ident: $t:ty = $constructor:ident, $feature:tt;)*) => {
+        $(
+            $(#[$attr])*
+            #[cfg(feature = $feature)]
+            #[derive(Clone, Debug)]
+            pub struct $name(CrcTable<$t>);
+
+            #[cfg(feature = $feature)]
+            impl Default for $name {
+                fn default() -> Self {
+                    $name(::catalog::$constructor())
+                }
+            }
+
+            #[cfg(feature = $feature)]
+            impl Deref for $name {
+                type Target = CrcTable<$t>;
+
+                fn deref(&self) -> &CrcTable<$t> {
+                    &self.0
+                }
+            }
+
+            #[cfg(feature = $feature)]
+            impl Borrow<CrcTable<$t>> for $name {
+                fn borrow(&self) -> &CrcTable<$t> {
+                    &self.0
+                }
+            }
+
+            #[cfg(feature = $feature)]
+            impl From<$name> for CrcTable<$t> {
+                fn from(spec: $name) -> Self {
+                    spec.0
+                }
+            }
+
+            #[cfg(feature = $feature)]
+            impl CrcSpec<$t> for $name {
+                fn width(&self) -> usize { self.0.width() }
+                fn poly(&self) -> $t { self.0.poly() }
+                fn init(&self) -> $t { self.0.init() }
+                fn refin(&self) -> bool { self.0.refin() }
+                fn refout(&self) -> bool { self.0.refout() }
+                fn xorout(&self) -> $t { self.0.xorout() }
+            }
+        )*
+    };
+}
+
+common! {
+    /// CRC-32/ISO-HDLC, the CRC-32 of Ethernet, zlib, PNG and ZIP.
+    Crc32: u32 = crc32_iso_hdlc, "catalog-crc32";
+    /// CRC-16/KERMIT, which RevEng lists as CRC-16/CCITT. The algorithm often called
+    /// CCITT-FALSE is [`catalog::crc16_ccitt_false`](../catalog/fn.crc16_ccitt_false.html).
+    Crc16Ccitt: u16 = crc16_kermit, "catalog-crc16";
+    /// CRC-16/MODBUS, used in the Modbus protocol.
+    Crc16Modbus: u16 = crc16_modbus, "catalog-crc16";
+    /// CRC-8/SMBUS, which RevEng lists as CRC-8.
+    Crc8: u8 = crc8_smbus, "catalog-crc8";
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "catalog-full")]
+    #[test]
+    fn check_values() {
+        use super::*;
+        assert_eq!(Crc32::default().check(), 0xCBF43926);
+        assert_eq!(Crc16Ccitt::default().check(), 0x2189);
+        assert_eq!(Crc16Modbus::default().check(), 0x4B37);
+        assert_eq!(Crc8::default().check(), 0xF4);
+    }
+
+    #[cfg(feature = "catalog-crc16")]
+    #[test]
+    fn same_as_catalog() {
+        use super::Crc16Modbus;
+        use primitive::CrcTable;
+        assert_eq!(::catalog::identify(&Crc16Modbus::default()), Some("CRC-16/MODBUS"));
+        assert!(CrcTable::from(Crc16Modbus::default()) == ::catalog::crc16_modbus());
+    }
+
+    #[cfg(feature = "catalog-crc32")]
+    #[test]
+    fn hashers() {
+        use super::Crc32;
+        use ::CrcHasher;
+        use primitive::CrcTableHasher;
+        let spec = Crc32::default();
+        let mut borrowed = CrcTableHasher::from(&*spec);
+        borrowed.update_from_slice(b"123456789");
+        let mut owned = CrcTableHasher::from(Crc32::default());
+        owned.update_from_slice(b"123456789");
+        assert_eq!((borrowed.finish(), owned.finish()), (0xCBF43926, 0xCBF43926));
+    }
+}
@@ -0,0 +1,161 @@
+//! Conversions for comparing checksums with the ones computed by other languages' libraries.
+//!
+//! Java's `java.util.zip.CRC32.getValue()` returns a zero-extended `long`,
+//! but the value is often truncated to an `int`, as are CRC-32 values stored in C's `int32_t`:
+//!
+//! ```
+//! use crc_rocksoft::interop::*;
+//!
+//! let crc = 0xCBF43926u32; // CRC-32 of "123456789"
+//! assert_eq!(to_i32(crc), -873187034); // (int) crc.getValue()
+//! assert_eq!(to_i64_zero_extended(crc), 3421780262); // crc.getValue()
+//! assert_eq!(from_java_int(-873187034), crc);
+//! ```
+
+use primitive::{ValueType, RegisterBytes};
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+
+/// Reinterprets a 32-bit checksum as a signed integer, like a cast to Java's `int` or C's `int32_t`.
+pub fn to_i32(crc: u32) -> i32 {
+    crc as i32
+}
+
+/// Converts a 32-bit checksum to a non-negative `i64`, like Java's `Checksum.getValue()`.
+pub fn to_i64_zero_extended(crc: u32) -> i64 {
+    i64::from(crc)
+}
+
+/// Reinterprets a signed 32-bit integer (e. g. Java's `int`) as a 32-bit checksum.
+pub fn from_java_int(value: i32) -> u32 {
+    value as u32
+}
+
+/// Converts the value returned by Java's `Checksum.getValue()` to a 32-bit checksum.
+///
+/// Returns `None` if the value is out of the range of `u32`,
+/// i. e. it didn't come from a 32-bit checksum.
+pub fn from_java_long(value: i64) -> Option<u32> {
+    u32::try_from(value).ok()
+}
+
+/// Returns the bytes of a checksum of the given width, most significant byte first.
+///
+/// The number of bytes is the smallest one that fits `width` bits,
+/// e. g. a CRC-24 checksum is three bytes long even if it's backed by `u32`.
+///
+/// ```
+/// use crc_rocksoft::interop::to_be_bytes;
+///
+/// assert!(to_be_bytes(0x21CF02u32, 24).eq([0x21, 0xCF, 0x02].iter().cloned()));
+/// ```
+pub fn to_be_bytes<T: ValueType>(crc: T, width: usize) -> Bytes<T> {
+    Bytes { bytes: crc.be_bytes(width), next: 0, value_type: PhantomData }
+}
+
+/// Returns the bytes of a checksum of the given width, least significant byte first.
+///
+/// The number of bytes is the same as for [`to_be_bytes`](fn.to_be_bytes.html).
+pub fn to_le_bytes<T: ValueType>(crc: T, width: usize) -> Bytes<T> {
+    Bytes { bytes: crc.le_bytes(width), next: 0, value_type: PhantomData }
+}
+
+/// The order in which the bytes of a checksum are stored, e. g. in a frame trailer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ByteOrder {
+    /// The most significant byte first.
+    BigEndian,
+    /// The least significant byte first.
+    LittleEndian,
+}
+
+/// An iterator over the bytes of a checksum.
+/// See [`to_be_bytes`](fn.to_be_bytes.html) and [`to_le_bytes`](fn.to_le_bytes.html).
+#[derive(Clone, Debug)]
+pub struct Bytes<T> {
+    bytes: RegisterBytes,
+    next: usize,
+    value_type: PhantomData<T>,
+}
+
+impl<T: ValueType> Iterator for Bytes<T> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.next)?;
+        self.next += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bytes.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: ValueType> ExactSizeIterator for Bytes<T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use super::*;
+    use primitive::{CrcTable, Wide};
+
+    fn crc32() -> u32 {
+        CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).check()
+    }
+
+    fn crc32c() -> u32 {
+        CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).check()
+    }
+
+    #[test]
+    fn java_crc32() {
+        // new CRC32().update("123456789".getBytes()); getValue() and (int) getValue()
+        assert_eq!(to_i64_zero_extended(crc32()), 3421780262);
+        assert_eq!(to_i32(crc32()), -873187034);
+        assert_eq!(from_java_int(-873187034), crc32());
+        assert_eq!(from_java_long(3421780262), Some(crc32()));
+    }
+
+    #[test]
+    fn java_crc32c() {
+        assert_eq!(to_i64_zero_extended(crc32c()), 3808858755);
+        assert_eq!(to_i32(crc32c()), -486108541);
+        assert_eq!(from_java_int(-486108541), crc32c());
+    }
+
+    #[test]
+    fn positive_values() {
+        assert_eq!(to_i32(0x765E7680), 0x765E7680);
+        assert_eq!(from_java_int(0x765E7680), 0x765E7680);
+    }
+
+    #[test]
+    fn java_long_out_of_range() {
+        assert_eq!(from_java_long(-1), None);
+        assert_eq!(from_java_long(0x1_0000_0000), None);
+        assert_eq!(from_java_long(0xFFFF_FFFF), Some(0xFFFF_FFFF));
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(to_be_bytes(crc32(), 32).collect::<Vec<_>>(), [0xCB, 0xF4, 0x39, 0x26]);
+        assert_eq!(to_le_bytes(crc32(), 32).collect::<Vec<_>>(), [0x26, 0x39, 0xF4, 0xCB]);
+        assert_eq!(to_be_bytes(0x21CF02u32, 24).collect::<Vec<_>>(), [0x21, 0xCF, 0x02]);
+        assert_eq!(to_le_bytes(0x21CF02u32, 24).collect::<Vec<_>>(), [0x02, 0xCF, 0x21]);
+        assert_eq!(to_be_bytes(0x19u8, 5).collect::<Vec<_>>(), [0x19]);
+        assert_eq!(to_be_bytes(0xDAFu16, 12).len(), 2);
+    }
+
+    #[test]
+    fn bytes_match_primitive() {
+        let value = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        assert_eq!(to_be_bytes(value, 128).collect::<Vec<_>>(), value.to_be_bytes());
+        assert_eq!(to_le_bytes(value, 128).collect::<Vec<_>>(), value.to_le_bytes());
+        assert_eq!(to_be_bytes(Wide(value.to_be_bytes()), 128).collect::<Vec<_>>(), value.to_be_bytes());
+        assert_eq!(to_le_bytes(0x995DC9BBDF1939FAu64, 64).collect::<Vec<_>>(), 0x995DC9BBDF1939FAu64.to_le_bytes());
+    }
+}
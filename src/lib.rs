@@ -3,18 +3,65 @@
 //!
 //! In his paper, Ross names the parameterized model the "Rocksoft™ Model"
 //! (for the company he was employed at), hence the name of this crate.
+//!
+//! # Features
+//!
+//! The crate is `no_std`; everything needed for computing checksums only depends on `core`.
+//!
+//! * `std` (enabled by default) adds conveniences that need the standard library,
+//!   such as `std::error::Error` implementations and the [`cache`](cache/index.html)
+//!   of lookup tables shared process-wide. Implies `alloc`.
+//! * `alloc` adds conveniences that need heap allocation.
+//! * `digest` implements the traits of the [`digest`](https://docs.rs/digest) crate
+//!   for `CrcTableHasher`.
+//! * `serde` implements `Serialize` and `Deserialize` for `CrcTable` and `CrcParams`.
+//! * `num-traits` adds the `NumValue` adapter, which makes any unsigned
+//!   [`num-traits`](https://docs.rs/num-traits) integer usable as a `ValueType`.
+//! * `rayon` adds `CrcTable::par_checksum`, which computes the checksum of a large slice
+//!   on all cores using [`rayon`](https://docs.rs/rayon). Implies `std`.
+//! * `hw` makes `CrcTable::checksum_with_state`, and so `CrcTableHasher::update_from_slice`,
+//!   use the CPU's CRC instructions for the algorithms they support, when the CPU has them:
+//!   CRC-32C with SSE4.2 on x86-64, the reflected algorithms with the CRC-32 poly
+//!   (`0x04C11DB7`, e. g. ISO-HDLC) with PCLMULQDQ on x86-64, for messages of 64 bytes or more,
+//!   and both of them with the CRC extension on AArch64. The CPU is checked at run time.
+//!   Implies `std`.
+//! * `codegen` adds the [`codegen`](codegen/index.html) module, which generates Rust source
+//!   for a spec with a precomputed lookup table, e. g. in a build script, and C source
+//!   for the lookup table. Implies `std`.
+//! * `catalog-crc8`, `catalog-crc16`, `catalog-crc32` and `catalog-crc64` each enable
+//!   the [`catalog`](catalog/index.html) algorithms backed by `u8`, `u16`, `u32` and `u64`
+//!   respectively, i. e. of widths up to 8, 16, 32 and 64 bits. `catalog-full` (enabled by default)
+//!   enables all of them; builds that need only a few algorithms can pick the families instead.
+//!   The [`common`](common/index.html) types, such as `Crc32`, come with their families.
+//! * `testkit` adds the [`testkit`](testkit/index.html) module with conformance checks
+//!   for third-party `CrcHasher` implementations.
 
-extern crate bit_reverse;
+#![no_std]
+
+#[cfg(any(feature = "std", test))] #[macro_use] extern crate std;
+#[cfg(feature = "alloc")] extern crate alloc;
+#[cfg(feature = "digest")] extern crate digest;
+#[cfg(feature = "serde")] extern crate serde;
+#[cfg(feature = "num-traits")] extern crate num_traits;
+#[cfg(feature = "rayon")] extern crate rayon;
 
 #[cfg(test)] #[macro_use] extern crate lazy_static;
+#[cfg(all(test, feature = "serde"))] extern crate serde_json;
+#[cfg(all(test, feature = "serde"))] extern crate toml;
 
 pub mod primitive;
+pub mod catalog;
+#[cfg(any(feature = "catalog-crc8", feature = "catalog-crc16", feature = "catalog-crc32", feature = "catalog-crc64"))]
+pub mod common;
+pub mod interop;
+#[cfg(feature = "codegen")] pub mod codegen;
+#[cfg(feature = "std")] pub mod cache;
+#[cfg(any(feature = "testkit", test))] pub mod testkit;
 
 /// A trait that provides accessors for elements of CRC algorithm specifications.
 ///
 /// The definitions of specification elements are taken from the
-/// [1993 paper by Ross Williams](http://www.ross.net/crc/crcpaper.html),
-/// with one notable exception (see [`refout()`](#tymethod.refout)).
+/// [1993 paper by Ross Williams](http://www.ross.net/crc/crcpaper.html).
 pub trait CrcSpec<T> {
     /// This is the width of the algorithm expressed in bits.
     /// This is one less than the width of the Poly.
@@ -33,6 +80,9 @@ pub trait CrcSpec<T> {
     /// when the algorithm starts. Unlike `poly`, this value is not affected
     /// by `refin` and `refout`; the bits of `init` are copied to the register
     /// verbatim. This parameter should be specified as a hexadecimal number.
+    ///
+    /// Note that table-driven implementations of reflected algorithms usually keep
+    /// the register reflected, in which case they have to reflect `init` before using it.
     fn init(&self) -> T;
 
     /// This is a boolean parameter. If it is FALSE, input bytes are
@@ -41,37 +91,48 @@ pub trait CrcSpec<T> {
     /// parameter is FALSE, each byte is reflected before being processed.
     fn refin(&self) -> bool;
 
-    /// This is a boolean parameter. If its value is the same as `refin`,
-    /// the final value in the register is fed into the XOROUT stage directly;
-    /// otherwise, the final register value is reflected first.
-    ///
-    /// Alternatively, you can think of this value as the indicator of whether
-    /// the result of the CRC computation should be reflected so that
-    /// the most significant bit of the `poly` value should correspond to
-    /// the result's zeroth bit (instead of the result's MSB).
-    ///
-    /// Note that this definition is different from the one given
-    /// in the paper by Ross Williams, which reads:
-    ///
-    /// ```plain
-    ///    REFOUT: This is a boolean parameter. If it is set to FALSE, the
-    ///    final value in the register is fed into the XOROUT stage directly,
-    ///    otherwise, if this parameter is TRUE, the final register value is
-    ///    reflected first.
-    /// ```
+    /// This is a boolean parameter. If it is set to FALSE, the
+    /// final value in the register is fed into the XOROUT stage directly,
+    /// otherwise, if this parameter is TRUE, the final register value is
+    /// reflected first.
     ///
-    /// With the current implementation (which, as far as I can determine,
-    /// is otherwise correct), the original definition would require
-    /// inverting `refout` values for all the documented specifications
-    /// if we want the implementation to emit the same check values.
-    /// Unfortunately, I couldn't find a specification that would use
-    /// `refin` unequal to `refout`, so verification is hard.
+    /// Table-driven implementations of reflected algorithms usually keep
+    /// the register reflected, so for them the final register value has to be
+    /// reflected only if `refout` differs from `refin`.
     fn refout(&self) -> bool;
 
     /// This is an `width`-bit value that should be specified as a
     /// hexadecimal number. It is XORed to the final register value (after
     /// the REFOUT stage) before the value is returned as the official checksum.
     fn xorout(&self) -> T;
+
+    /// Returns the poly in the reversed (reflected) notation, e. g. `0xEDB88320` for CRC-32.
+    ///
+    /// `poly` is the notation of the Rocksoft™ Model and of most CRC catalogues.
+    /// The reversed one is common in descriptions and implementations of reflected algorithms,
+    /// since it's the value XORed into their register, which is kept reflected;
+    /// use this when porting such code, or comparing against a hard-coded reflected table.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    ///
+    /// assert_eq!(catalog::crc32_iso_hdlc().reflected_poly(), 0xEDB88320);
+    /// assert_eq!(catalog::crc16_arc().reflected_poly(), 0xA001);
+    /// ```
+    fn reflected_poly(&self) -> T where T: primitive::ValueType {
+        primitive::poly::reversed(self.poly(), self.width())
+    }
+}
+
+/// A reference to a spec is a spec, so that types generic over a spec,
+/// e. g. [`CrcBitwiseHasher`](primitive/struct.CrcBitwiseHasher.html), can borrow it.
+impl<T, S: CrcSpec<T> + ?Sized> CrcSpec<T> for &S {
+    fn width(&self) -> usize { (**self).width() }
+    fn poly(&self) -> T { (**self).poly() }
+    fn init(&self) -> T { (**self).init() }
+    fn refin(&self) -> bool { (**self).refin() }
+    fn refout(&self) -> bool { (**self).refout() }
+    fn xorout(&self) -> T { (**self).xorout() }
 }
 
 /// A basic trait for an object that computes a CRC hash in its own mutable internal state.
@@ -101,4 +162,70 @@ pub trait CrcHasher<T> {
             self.update(b);
         }
     }
+
+    /// Update the internal state with all the bytes read from `reader` until the end of the input,
+    /// and return the number of bytes read. The data is read in chunks into a buffer on the stack
+    /// and passed to `update_from_slice`. Reads interrupted by the OS are retried.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let mut hasher = CrcTableHasher::from(catalog::crc32_iso_hdlc());
+    /// let mut file: &[u8] = b"123456789"; // or a std::fs::File
+    /// assert_eq!(hasher.update_from_reader(&mut file).unwrap(), 9);
+    /// assert_eq!(hasher.finish(), 0xCBF43926);
+    /// ```
+    #[cfg(feature = "std")]
+    fn update_from_reader<R: std::io::Read + ?Sized>(&mut self, reader: &mut R) -> std::io::Result<u64>
+        where Self: Sized
+    {
+        let mut buffer = [0u8; 8192];
+        let mut total = 0;
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(total),
+                Ok(n) => {
+                    self.update_from_slice(&buffer[..n]);
+                    total += n as u64;
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// A `CrcHasher` that also accepts single bits of user data, for messages
+/// whose length in bits is not a multiple of 8.
+///
+/// It's separate from `CrcHasher`, so that hashers that can only process whole bytes,
+/// e. g. ones backed by a CRC peripheral, don't have to implement it.
+pub trait CrcBitInput<T>: CrcHasher<T> {
+
+    /// Update the internal state with a single bit of user data. A whole byte is equivalent
+    /// to its eight bits, starting from the most significant one, or from the least significant one
+    /// if `refin` is set.
+    fn update_bit(&mut self, bit: bool);
+
+    /// Update the internal state with the first `nbits` bits of `byte`, for messages ending with
+    /// a byte of which only some bits are meaningful. The bits are the ones that `update`
+    /// would process first: the `nbits` most significant bits of `byte`, or the least significant
+    /// ones if `refin` is set. `update_last_bits(byte, 8)` is equivalent to `update(byte)`.
+    ///
+    /// The default implementation feeds the bits to `update_bit` one by one;
+    /// table-driven hashers override it. Panics if `nbits` is greater than 8.
+    fn update_last_bits(&mut self, byte: u8, nbits: u8) {
+        assert!(nbits <= 8, "can't process more than 8 bits at once: {}", nbits);
+        for i in 0..nbits {
+            let bit = if self.refin() { byte >> i } else { byte >> (7 - i) };
+            self.update_bit(bit & 1 == 1);
+        }
+    }
+
+    /// Returns the `refin` parameter of the algorithm, which defines the order
+    /// in which `update_last_bits` takes the bits of a byte.
+    fn refin(&self) -> bool;
 }
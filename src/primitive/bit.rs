@@ -0,0 +1,212 @@
+use ::{CrcSpec, CrcHasher, CrcBitInput};
+use super::{ValueType, CrcParams, reflect};
+use super::reference::{reference_update, reference_update_bit};
+
+
+/// An implementation of `CrcHasher` without a lookup table, which computes the checksum
+/// one bit at a time with shifts and XORs, straight from the parameters of any spec.
+///
+/// It's an order of magnitude slower than the table-driven hashers, but it needs no
+/// precomputation and no memory besides the spec and the register, which suits
+/// short messages hashed once, e. g. at boot. Any width up to the bit size of `T`
+/// and any combination of `refin` and `refout` is supported.
+///
+/// The hasher holds on to the spec itself, be it a reference to a `CrcTable`, a
+/// [`catalog`](../catalog/index.html) entry or `CrcParams`; a spec that isn't used
+/// for anything else doesn't have to compute its table, so the table-less hasher
+/// costs no code or memory for one. It also serves as an oracle for any table-driven
+/// implementation, since it shares no code with them.
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+/// let mut bitwise = CrcBitwiseHasher::from(&spec);
+/// bitwise.update_from_slice(b"123456789");
+/// assert_eq!(bitwise.finish(), spec.check());
+///
+/// let params = CrcParams { width: 16, poly: 0x1021u16, init: 0, refin: false, refout: false, xorout: 0 };
+/// let mut xmodem = CrcBitwiseHasher::from(params);
+/// xmodem.update_from_slice(b"123456789");
+/// assert_eq!(xmodem.finish(), 0x31C3);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CrcBitwiseHasher<T, S: CrcSpec<T>> {
+    /// The unreflected register, as in the [reference](reference/index.html) implementation.
+    register: T,
+    spec: S
+}
+
+/// A [`CrcBitwiseHasher`](struct.CrcBitwiseHasher.html) that owns a copy of the parameters
+/// of the algorithm.
+///
+/// The constructor is a `const fn`, so a hasher can be a constant and be copied
+/// whenever a checksum is needed:
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// const CRC32: CrcBitHasher<u32> = CrcBitHasher::new(CrcParams {
+///     width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF
+/// });
+///
+/// let mut hasher = CRC32;
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), 0xCBF43926);
+/// ```
+pub type CrcBitHasher<T> = CrcBitwiseHasher<T, CrcParams<T>>;
+
+impl<T: ValueType, S: CrcSpec<T>> CrcBitwiseHasher<T, S> {
+    /// Returns the spec used by this hasher.
+    pub fn spec(&self) -> &S {
+        &self.spec
+    }
+}
+
+impl<T: ValueType> CrcBitwiseHasher<T, CrcParams<T>> {
+
+    /// Constructs a hasher for the algorithm with the given parameters.
+    ///
+    /// The parameters aren't validated, since this is a `const fn`; a width that isn't supported
+    /// by `T` makes the hasher panic. Parameters taken from an existing spec, e. g. a `CrcTable`,
+    /// have already been validated; see [`from_spec`](#method.from_spec).
+    pub const fn new(params: CrcParams<T>) -> Self {
+        CrcBitwiseHasher { register: params.init, spec: params }
+    }
+
+    /// Constructs a hasher for the algorithm of any spec, e. g. a `CrcTable`,
+    /// a [`catalog`](../catalog/index.html) entry or `CrcParams`.
+    pub fn from_spec(spec: &impl CrcSpec<T>) -> Self {
+        CrcBitwiseHasher::new(CrcParams::from_spec(spec))
+    }
+
+    /// Returns the parameters of the algorithm.
+    pub fn params(&self) -> CrcParams<T> {
+        self.spec
+    }
+
+}
+
+impl<T: ValueType, S: CrcSpec<T>> CrcHasher<T> for CrcBitwiseHasher<T, S> {
+    fn reset(&mut self) {
+        self.register = self.spec.init();
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.register = reference_update(&self.spec, self.register, byte);
+    }
+
+    fn finish(&self) -> T {
+        let width = self.spec.width();
+        (if self.spec.refout() { reflect(self.register, width) } else { self.register }) ^ self.spec.xorout()
+    }
+}
+
+impl<T: ValueType, S: CrcSpec<T>> CrcBitInput<T> for CrcBitwiseHasher<T, S> {
+    fn update_bit(&mut self, bit: bool) {
+        self.register = reference_update_bit(&self.spec, self.register, bit);
+    }
+
+    fn refin(&self) -> bool {
+        self.spec.refin()
+    }
+}
+
+impl<T: ValueType, S: CrcSpec<T>> From<S> for CrcBitwiseHasher<T, S> {
+    /// Constructs and returns a hasher from a spec or a reference to one.
+    /// The parameters aren't validated; a width that isn't supported by `T` makes the hasher panic.
+    fn from(spec: S) -> Self {
+        CrcBitwiseHasher { register: spec.init(), spec }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use primitive::test_util::Random;
+    use ::CrcHasher;
+    use super::super::{ValueType, CrcParams, CrcTable, CrcTableHasher};
+    use super::{CrcBitHasher, CrcBitwiseHasher};
+
+    fn data() -> Vec<u8> {
+        Random(0x2545F4914F6CDD1D).data(300)
+    }
+
+    fn assert_same<T: ValueType>(spec: &CrcTable<T>) {
+        let data = data();
+        for len in (0..20).chain(Some(data.len())) {
+            let mut expected = CrcTableHasher::from(spec);
+            expected.update_from_slice(&data[..len]);
+            let mut actual = CrcBitHasher::from_spec(spec);
+            actual.update_from_slice(&data[..len]);
+            assert_eq!(actual.finish(), expected.finish(), "{:?}, len: {}", spec, len);
+        }
+    }
+
+    #[test]
+    fn catalog_entries() {
+        use catalog::{all, CatalogSpec};
+        for entry in all() {
+            match entry.spec() {
+                CatalogSpec::U8(spec) => assert_same(&spec),
+                CatalogSpec::U16(spec) => assert_same(&spec),
+                CatalogSpec::U32(spec) => assert_same(&spec),
+                CatalogSpec::U64(spec) => assert_same(&spec),
+            }
+        }
+    }
+
+    #[test]
+    fn reflection_and_width() {
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            assert_same(&CrcTable::new_with_width(3, 0x3u8, 0x5, refin, refout, 0x7));
+            assert_same(&CrcTable::new_with_width(12, 0x80Fu16, 0x123, refin, refout, 0));
+            assert_same(&CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, refin, refout, 0x5A5A5A));
+            assert_same(&CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 1, refin, refout, 0));
+        }
+    }
+
+    #[test]
+    fn conformance() {
+        use testkit::assert_bit_input_conformance;
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            let spec = CrcTable::new_with_width(5, 0x05u8, 0x1F, refin, refout, 0x1F);
+            assert_bit_input_conformance(|| CrcBitHasher::from_spec(&spec), &spec, &[]);
+        }
+    }
+
+    #[test]
+    fn bitwise_conformance() {
+        use testkit::assert_bit_input_conformance;
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            let spec = CrcTable::new_with_width(12, 0x80Fu16, 0x123, refin, refout, 0);
+            assert_bit_input_conformance(|| CrcBitwiseHasher::from(&spec), &spec, &[]);
+        }
+    }
+
+    #[test]
+    fn bitwise_from_params() {
+        let params = CrcParams { width: 32, poly: 0x04C11DB7u32, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF };
+        let mut hasher = CrcBitwiseHasher::from(params);
+        hasher.update_from_slice(b"123456789");
+        assert_eq!(hasher.finish(), 0xCBF43926);
+        assert_eq!(*hasher.spec(), params);
+        hasher.reset();
+        assert_eq!(hasher.finish(), 0);
+    }
+
+    static CRC16: CrcBitHasher<u16> = CrcBitHasher::new(CrcParams {
+        width: 16, poly: 0x1021, init: 0, refin: true, refout: true, xorout: 0
+    });
+
+    #[test]
+    fn in_static() {
+        let mut hasher = CRC16;
+        hasher.update_from_slice(b"123456789");
+        assert_eq!(hasher.finish(), 0x2189);
+        assert_eq!(CRC16.finish(), 0);
+        assert_eq!(CRC16.params(), CrcParams { width: 16, poly: 0x1021, init: 0, refin: true, refout: true, xorout: 0 });
+    }
+}
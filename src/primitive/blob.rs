@@ -0,0 +1,271 @@
+#[cfg(feature = "alloc")] use ::CrcSpec;
+use super::{ValueType, CrcParams, CrcTable, CrcError, Table, find_mismatch};
+use super::spec::validate;
+use interop::ByteOrder;
+use core::fmt;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+
+/// The first bytes of an exported table.
+const MAGIC: [u8; 4] = *b"CRCT";
+
+/// The version of the format written by `export_bytes`.
+const VERSION: u8 = 1;
+
+/// The length of the header preceding the entries.
+const HEADER_LEN: usize = 8;
+
+impl<T: ValueType> CrcTable<T> {
+    /// Serializes the lookup table into a blob, which can be turned back into a spec
+    /// with [`import_bytes`](#method.import_bytes) without computing the table, e. g. on
+    /// a slow embedded target. The parameters are not included; they're passed to `import_bytes`.
+    ///
+    /// Only available with the `alloc` feature.
+    ///
+    /// The blob consists of an 8-byte header:
+    ///
+    /// | Offset | Length | Contents                                           |
+    /// |--------|--------|----------------------------------------------------|
+    /// | 0      | 4      | `CRCT` in ASCII                                    |
+    /// | 4      | 1      | the version of the format, currently 1             |
+    /// | 5      | 1      | the byte order of the entries: 0 for little-endian, 1 for big-endian |
+    /// | 6      | 2      | the width of the algorithm, little-endian          |
+    ///
+    /// followed by the 256 entries of the table, each `(width + 7) / 8` bytes long.
+    /// This method always writes the entries in little-endian byte order regardless
+    /// of the host, so the blob is the same on every platform; `import_bytes` accepts both.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// let blob = crc32.export_bytes();
+    /// assert_eq!(blob.len(), 8 + 256 * 4);
+    /// assert_eq!(&blob[..8], b"CRCT\x01\x00\x20\x00");
+    /// // The entry for the byte 1 is 0x77073096.
+    /// assert_eq!(&blob[12..16], &[0x96, 0x30, 0x07, 0x77]);
+    ///
+    /// let imported = CrcTable::import_bytes(crc32.params(), &blob).unwrap();
+    /// assert_eq!(imported.check(), 0xCBF43926);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn export_bytes(&self) -> Vec<u8> {
+        let width = self.width();
+        let mut blob = Vec::with_capacity(HEADER_LEN + 256 * width.div_ceil(8));
+        blob.extend_from_slice(&MAGIC);
+        blob.push(VERSION);
+        blob.push(0);
+        blob.extend_from_slice(&(width as u16).to_le_bytes());
+        for entry in self.table().iter() {
+            blob.extend_from_slice(&entry.le_bytes(width));
+        }
+        blob
+    }
+
+    /// Constructs a spec from its parameters and a lookup table exported with
+    /// [`export_bytes`](#method.export_bytes), which describes the format.
+    ///
+    /// Besides the header, the table is checked against the parameters; see `find_mismatch` in `table.rs`.
+    ///
+    /// Returns an error if the parameters are invalid (see [`try_new`](#method.try_new)),
+    /// if the header is malformed or doesn't match the width, or if the table doesn't match.
+    pub fn import_bytes(params: CrcParams<T>, bytes: &[u8]) -> Result<CrcTable<T>, ImportError<T>> {
+        let width = params.width;
+        validate(width, params.poly, params.init, params.xorout).map_err(ImportError::InvalidParams)?;
+        if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC {
+            return Err(ImportError::BadMagic);
+        }
+        if bytes[4] != VERSION {
+            return Err(ImportError::UnsupportedVersion(bytes[4]));
+        }
+        let order = match bytes[5] {
+            0 => ByteOrder::LittleEndian,
+            1 => ByteOrder::BigEndian,
+            other => return Err(ImportError::BadByteOrder(other)),
+        };
+        let blob_width = usize::from(u16::from_le_bytes([bytes[6], bytes[7]]));
+        if blob_width != width {
+            return Err(ImportError::WidthMismatch { expected: width, actual: blob_width });
+        }
+        let entry_len = width.div_ceil(8);
+        let entries = &bytes[HEADER_LEN..];
+        if entries.len() != 256 * entry_len {
+            return Err(ImportError::BadLength(bytes.len()));
+        }
+
+        let mut table: Table<T> = [T::from(0); 256];
+        for (entry, bytes) in table.iter_mut().zip(entries.chunks_exact(entry_len)) {
+            *entry = bytes.iter().enumerate().fold(T::from(0), |value, (i, &b)| {
+                let shift = match order {
+                    ByteOrder::LittleEndian => i,
+                    ByteOrder::BigEndian => entry_len - 1 - i,
+                };
+                value ^ (T::from(b) << ((shift * 8) as u8))
+            });
+        }
+        if let Some(index) = find_mismatch(&params, &table) {
+            return Err(ImportError::EntryMismatch(index));
+        }
+        Ok(CrcTable::from_const_table_with_width(width, params.poly, params.init, params.refin, params.refout, params.xorout, &table))
+    }
+}
+
+/// The error type for [`CrcTable::import_bytes`](struct.CrcTable.html#method.import_bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError<T> {
+    /// The parameters can't be used for computing checksums.
+    InvalidParams(CrcError<T>),
+    /// The data doesn't start with the header of an exported table.
+    BadMagic,
+    /// The data was exported in a version of the format that isn't supported.
+    UnsupportedVersion(u8),
+    /// The byte order in the header is invalid.
+    BadByteOrder(u8),
+    /// The table was exported for a different width than that of the parameters.
+    WidthMismatch {
+        /// The width of the parameters.
+        expected: usize,
+        /// The width in the header.
+        actual: usize
+    },
+    /// The data is too short or too long for a table of its width; this is its length.
+    BadLength(usize),
+    /// The entry of the table for this byte doesn't match the parameters.
+    EntryMismatch(u8),
+}
+
+impl<T: ValueType> fmt::Display for ImportError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImportError::InvalidParams(ref error) =>
+                write!(f, "invalid parameters: {}", error),
+            ImportError::BadMagic =>
+                write!(f, "not an exported table"),
+            ImportError::UnsupportedVersion(version) =>
+                write!(f, "unsupported version of the format: {}", version),
+            ImportError::BadByteOrder(order) =>
+                write!(f, "invalid byte order: {}", order),
+            ImportError::WidthMismatch { expected, actual } =>
+                write!(f, "the table is for width {}, not {}", actual, expected),
+            ImportError::BadLength(len) =>
+                write!(f, "invalid length of an exported table: {}", len),
+            ImportError::EntryMismatch(index) =>
+                write!(f, "the entry for {:#04x} doesn't match the parameters", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ValueType> ::std::error::Error for ImportError<T> {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::vec::Vec;
+    use ::CrcSpec;
+    use super::super::{ValueType, CrcParams, CrcTable, CrcError};
+    use super::ImportError;
+
+    fn crc32() -> CrcTable<u32> {
+        CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF)
+    }
+
+    fn assert_round_trip<T: ValueType>(spec: CrcTable<T>) {
+        let blob = spec.export_bytes();
+        assert_eq!(blob.len(), 8 + 256 * spec.width().div_ceil(8));
+        let imported = CrcTable::import_bytes(spec.params(), &blob).unwrap();
+        assert!(imported == spec);
+        assert_eq!(&imported.table()[..], &spec.table()[..]);
+    }
+
+    #[test]
+    fn round_trip() {
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            assert_round_trip(CrcTable::new_with_width(5, 0x05u8, 0x1F, refin, refout, 0x1F));
+            assert_round_trip(CrcTable::new(0x8005u16, 0, refin, refout, 0));
+            assert_round_trip(CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, refin, refout, 0));
+            assert_round_trip(CrcTable::new(0x42F0E1EBA9EA3693u64, !0, refin, refout, !0));
+            assert_round_trip(CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0, refin, refout, 0));
+        }
+    }
+
+    #[test]
+    fn little_endian_on_disk() {
+        let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, false, false, 0);
+        let blob = spec.export_bytes();
+        assert_eq!(&blob[..8], &[b'C', b'R', b'C', b'T', 1, 0, 24, 0]);
+        // Entry 1 is the poly itself, in 3 bytes.
+        assert_eq!(&blob[8 + 3..8 + 6], &[0xFB, 0x4C, 0x86]);
+    }
+
+    #[test]
+    fn big_endian_import() {
+        let spec = crc32();
+        let mut blob = spec.export_bytes();
+        blob[5] = 1;
+        for entry in blob[8..].chunks_mut(4) {
+            entry.reverse();
+        }
+        let imported = CrcTable::import_bytes(spec.params(), &blob).unwrap();
+        assert_eq!(&imported.table()[..], &spec.table()[..]);
+        assert_eq!(imported.check(), 0xCBF43926);
+    }
+
+    #[test]
+    fn errors() {
+        let spec = crc32();
+        let params = spec.params();
+        let blob = spec.export_bytes();
+        let import = |blob: &[u8]| CrcTable::import_bytes(params, blob).err();
+        let modified = |offset: usize, byte: u8| {
+            let mut blob = blob.clone();
+            blob[offset] = byte;
+            blob
+        };
+        assert_eq!(import(&blob), None);
+        assert_eq!(import(b""), Some(ImportError::BadMagic));
+        assert_eq!(import(&modified(0, b'X')), Some(ImportError::BadMagic));
+        assert_eq!(import(&modified(4, 2)), Some(ImportError::UnsupportedVersion(2)));
+        assert_eq!(import(&modified(5, 2)), Some(ImportError::BadByteOrder(2)));
+        assert_eq!(import(&modified(6, 16)), Some(ImportError::WidthMismatch { expected: 32, actual: 16 }));
+        assert_eq!(import(&blob[..blob.len() - 1]), Some(ImportError::BadLength(blob.len() - 1)));
+        let mut longer = blob.clone();
+        longer.push(0);
+        assert_eq!(import(&longer), Some(ImportError::BadLength(blob.len() + 1)));
+        assert_eq!(
+            CrcTable::import_bytes(CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF).params(), &blob).err(),
+            Some(ImportError::EntryMismatch(0x01))
+        );
+        assert_eq!(
+            CrcTable::import_bytes(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, false, true, 0xFFFFFFFF).params(), &blob).err(),
+            Some(ImportError::EntryMismatch(0x01))
+        );
+        assert_eq!(
+            CrcTable::import_bytes(CrcParams { poly: 0, ..params }, &blob).err(),
+            Some(ImportError::InvalidParams(CrcError::ZeroPoly))
+        );
+    }
+
+    #[test]
+    fn corrupted_entries() {
+        let spec = crc32();
+        let blob = spec.export_bytes();
+        for index in 0..256 {
+            for &bit in &[0, 13, 31] {
+                let mut corrupted: Vec<u8> = blob.clone();
+                corrupted[8 + index * 4 + bit / 8] ^= 1 << (bit % 8);
+                let error = CrcTable::import_bytes(spec.params(), &corrupted).err();
+                assert_eq!(error, Some(ImportError::EntryMismatch(index as u8)), "bit: {}", bit);
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        use std::string::ToString;
+        assert_eq!(ImportError::<u32>::EntryMismatch(0x37).to_string(), "the entry for 0x37 doesn't match the parameters");
+        assert_eq!(
+            ImportError::<u32>::WidthMismatch { expected: 32, actual: 16 }.to_string(),
+            "the table is for width 16, not 32"
+        );
+    }
+}
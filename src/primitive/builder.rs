@@ -0,0 +1,193 @@
+use super::{ValueType, CrcTable, CrcError};
+use super::poly::from_koopman;
+
+
+/// A builder for `CrcTable`, for when positional constructor arguments are too easy to mix up.
+///
+/// `refin` and `refout` default to `false`, `init` and `xorout` to zero,
+/// and the width to the [default width](trait.ValueType.html#method.default_width) of `T`.
+/// The poly has no default.
+///
+/// ```
+/// use crc_rocksoft::primitive::CrcTableBuilder;
+///
+/// let spec = CrcTableBuilder::new()
+///     .poly(0x04C11DB7u32)
+///     .init(0xFFFFFFFF)
+///     .refin(true)
+///     .refout(true)
+///     .xorout(0xFFFFFFFF)
+///     .build()
+///     .unwrap();
+/// assert_eq!(spec.check(), 0xCBF43926);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CrcTableBuilder<T> {
+    width: Option<usize>,
+    poly: Option<T>,
+    koopman: bool,
+    init: Option<T>,
+    refin: bool,
+    refout: bool,
+    xorout: Option<T>,
+}
+
+impl<T: ValueType> CrcTableBuilder<T> {
+
+    /// Creates a builder with the default parameters.
+    pub fn new() -> Self {
+        CrcTableBuilder { width: None, poly: None, koopman: false, init: None, refin: false, refout: false, xorout: None }
+    }
+
+    /// Sets the width of the algorithm.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the poly, in the same notation as `CrcTable::new`.
+    pub fn poly(mut self, poly: T) -> Self {
+        self.poly = Some(poly);
+        self.koopman = false;
+        self
+    }
+
+    /// Sets the poly in Koopman notation, as found in Koopman's polynomial tables.
+    /// It's converted with [`poly::from_koopman`](poly/fn.from_koopman.html)
+    /// for the width of the algorithm when the spec is built.
+    pub fn poly_koopman(mut self, koopman_poly: T) -> Self {
+        self.poly = Some(koopman_poly);
+        self.koopman = true;
+        self
+    }
+
+    /// Sets the initial value of the register.
+    pub fn init(mut self, init: T) -> Self {
+        self.init = Some(init);
+        self
+    }
+
+    /// Sets whether input bytes are reflected.
+    pub fn refin(mut self, refin: bool) -> Self {
+        self.refin = refin;
+        self
+    }
+
+    /// Sets whether the final value of the register is reflected.
+    pub fn refout(mut self, refout: bool) -> Self {
+        self.refout = refout;
+        self
+    }
+
+    /// Sets both `refin` and `refout`, which are equal for most algorithms.
+    pub fn reflect(self, reflect: bool) -> Self {
+        self.refin(reflect).refout(reflect)
+    }
+
+    /// Sets the value XORed to the final value of the register.
+    pub fn xorout(mut self, xorout: T) -> Self {
+        self.xorout = Some(xorout);
+        self
+    }
+
+    /// Builds the spec. The values are truncated to the width of the algorithm,
+    /// so e. g. `!0` can be used for an all-ones `init` of any width.
+    ///
+    /// Returns an error if the poly is missing or is zero after truncation,
+    /// or if the width is zero or greater than the bit size of `T`.
+    pub fn build(&self) -> Result<CrcTable<T>, CrcError<T>> {
+        let width = self.width.unwrap_or_else(T::default_width);
+        if width == 0 || width > T::BITS as usize {
+            return Err(CrcError::UnsupportedWidth(width));
+        }
+        let poly = self.poly.ok_or(CrcError::MissingField("poly"))?;
+        let poly = if self.koopman { from_koopman(poly, width) } else { poly };
+        let mask = T::mask(width);
+        let zero = T::from(0);
+        CrcTable::try_new_with_width(
+            width,
+            poly & mask,
+            self.init.unwrap_or(zero) & mask,
+            self.refin,
+            self.refout,
+            self.xorout.unwrap_or(zero) & mask,
+        )
+    }
+}
+
+impl<T: ValueType> Default for CrcTableBuilder<T> {
+    fn default() -> Self {
+        CrcTableBuilder::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::CrcSpec;
+    use super::CrcTableBuilder;
+    use super::super::CrcError;
+
+    #[test]
+    fn defaults() {
+        let spec = CrcTableBuilder::new().poly(0x1021u16).build().unwrap();
+        assert_eq!(spec.width(), 16);
+        assert_eq!(spec.init(), 0);
+        assert!(!spec.refin());
+        assert!(!spec.refout());
+        assert_eq!(spec.xorout(), 0);
+        assert_eq!(spec.check(), 0x31C3);
+    }
+
+    #[test]
+    fn any_order() {
+        let spec = CrcTableBuilder::new()
+            .xorout(0u16)
+            .refout(true)
+            .init(0xFFFF)
+            .refin(true)
+            .poly(0x8005)
+            .build()
+            .unwrap();
+        assert_eq!(spec.check(), 0x4B37);
+    }
+
+    #[test]
+    fn masks_to_width() {
+        let spec = CrcTableBuilder::new().width(5).poly(0x05u8).init(!0).refin(true).refout(true).xorout(!0).build().unwrap();
+        assert_eq!(spec.init(), 0x1F);
+        assert_eq!(spec.xorout(), 0x1F);
+        assert_eq!(spec.check(), 0x19);
+        let spec = CrcTableBuilder::new().width(24).poly(0xFF864CFBu32).init(0xB704CE).build().unwrap();
+        assert_eq!(spec.poly(), 0x864CFB);
+        assert_eq!(spec.check(), 0x21CF02);
+    }
+
+    #[test]
+    fn reflect() {
+        let spec = CrcTableBuilder::new().poly(0x04C11DB7u32).init(!0).reflect(true).xorout(!0).build().unwrap();
+        assert!(spec.refin() && spec.refout());
+        assert_eq!(spec.check(), 0xCBF43926);
+        let spec = CrcTableBuilder::new().poly(0x80Fu16).width(12).reflect(true).refin(false).build().unwrap();
+        assert!(!spec.refin() && spec.refout());
+        assert_eq!(spec.check(), 0xDAF);
+    }
+
+    #[test]
+    fn poly_koopman() {
+        let spec = CrcTableBuilder::new().poly_koopman(0x82608EDBu32).init(!0).reflect(true).xorout(!0).build().unwrap();
+        assert_eq!(spec.poly(), 0x04C11DB7);
+        let spec = CrcTableBuilder::new().width(12).poly_koopman(0xC07u16).reflect(false).refout(true).build().unwrap();
+        assert_eq!(spec.poly(), 0x80F);
+        let spec = CrcTableBuilder::new().poly_koopman(0x82608EDBu32).poly(0x1EDC6F41).build().unwrap();
+        assert_eq!(spec.poly(), 0x1EDC6F41);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(CrcTableBuilder::<u32>::new().build().err(), Some(CrcError::MissingField("poly")));
+        assert_eq!(CrcTableBuilder::new().poly(0x07u8).width(9).build().err(), Some(CrcError::UnsupportedWidth(9)));
+        assert_eq!(CrcTableBuilder::new().poly(0x07u8).width(0).build().err(), Some(CrcError::UnsupportedWidth(0)));
+        assert_eq!(CrcTableBuilder::new().poly(0x80u8).width(7).build().err(), Some(CrcError::ZeroPoly));
+    }
+}
@@ -0,0 +1,79 @@
+//! Implementations of the traits from the `digest` crate, for interoperability
+//! with the RustCrypto ecosystem. Only available with the `digest` feature.
+//!
+//! The output size is determined by the register type, e. g. a `CrcTableHasher<u32, _>`
+//! produces four bytes. The checksum is written in big-endian byte order; checksums of
+//! algorithms narrower than the register (like CRC-24 backed by `u32`) are zero-padded
+//! at the front.
+
+use ::CrcHasher;
+use super::{CrcTable, CrcTableHasher};
+use core::borrow::Borrow;
+use digest::{Update, OutputSizeUser, FixedOutput, FixedOutputReset, Reset, Output};
+use digest::consts::{U1, U2, U4, U8, U16};
+
+macro_rules! impl_digest {
+    ($t:ty, $size:ty) => {
+        impl<S: Borrow<CrcTable<$t>>> Update for CrcTableHasher<$t, S> {
+            fn update(&mut self, data: &[u8]) {
+                self.update_from_slice(data);
+            }
+        }
+
+        impl<S: Borrow<CrcTable<$t>>> OutputSizeUser for CrcTableHasher<$t, S> {
+            type OutputSize = $size;
+        }
+
+        impl<S: Borrow<CrcTable<$t>>> FixedOutput for CrcTableHasher<$t, S> {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                out.copy_from_slice(&CrcHasher::finish(&self).to_be_bytes());
+            }
+        }
+
+        impl<S: Borrow<CrcTable<$t>>> Reset for CrcTableHasher<$t, S> {
+            fn reset(&mut self) {
+                CrcHasher::reset(self);
+            }
+        }
+
+        impl<S: Borrow<CrcTable<$t>>> FixedOutputReset for CrcTableHasher<$t, S> {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                out.copy_from_slice(&CrcHasher::finish(self).to_be_bytes());
+                CrcHasher::reset(self);
+            }
+        }
+    };
+}
+impl_digest!(u8, U1);
+impl_digest!(u16, U2);
+impl_digest!(u32, U4);
+impl_digest!(u64, U8);
+impl_digest!(u128, U16);
+
+#[cfg(test)]
+mod tests {
+    use digest::{Update, FixedOutput, FixedOutputReset};
+    use super::super::{CrcTable, CrcTableHasher};
+
+    #[test]
+    fn crc32() {
+        let mut h = CrcTableHasher::from(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+        Update::update(&mut h, b"123456789");
+        assert_eq!(&h.finalize_fixed()[..], &[0xCB, 0xF4, 0x39, 0x26]);
+    }
+
+    #[test]
+    fn crc16_reset() {
+        let mut h = CrcTableHasher::from(CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16));
+        Update::update(&mut h, b"123456789");
+        assert_eq!(&h.finalize_fixed_reset()[..], &[0x29, 0xB1]);
+        Update::update(&mut h, b"123456789");
+        assert_eq!(&h.finalize_fixed_reset()[..], &[0x29, 0xB1]);
+    }
+
+    #[test]
+    fn crc24_is_zero_padded() {
+        let h = CrcTableHasher::from(CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32));
+        assert_eq!(&h.chain(b"123456789").finalize_fixed()[..], &[0x00, 0x21, 0xCF, 0x02]);
+    }
+}
@@ -0,0 +1,78 @@
+use super::ValueType;
+use core::fmt;
+
+
+/// The error type for CRC algorithm specifications that can't be used
+/// for computing checksums.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrcError<T> {
+    /// The width is not supported for the register type.
+    UnsupportedWidth(usize),
+    /// The poly is zero, which makes every checksum depend on `init` and `xorout` only.
+    ZeroPoly,
+    /// One of the parameters has bits set beyond the width of the algorithm.
+    ValueExceedsWidth {
+        /// The name of the offending parameter.
+        field: &'static str,
+        /// The offending value.
+        value: T
+    },
+    /// A required parameter is missing from a parsed specification.
+    MissingField(&'static str),
+    /// A parameter of a parsed specification is malformed,
+    /// or doesn't fit in the register type.
+    InvalidField(&'static str),
+    /// A parameter is listed more than once in a parsed specification.
+    DuplicateField(&'static str),
+    /// The `check` or `residue` value listed in a parsed specification
+    /// differs from the one computed for the algorithm.
+    Mismatch {
+        /// The name of the offending parameter.
+        field: &'static str,
+        /// The listed value.
+        expected: T,
+        /// The computed value.
+        actual: T
+    }
+}
+
+impl<T: ValueType> fmt::Display for CrcError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CrcError::UnsupportedWidth(width) =>
+                write!(f, "unsupported width: {}", width),
+            CrcError::ZeroPoly =>
+                write!(f, "the poly must not be zero"),
+            CrcError::ValueExceedsWidth { field, value } =>
+                write!(f, "the value of {} ({:?}) doesn't fit in the width of the algorithm", field, value),
+            CrcError::MissingField(field) =>
+                write!(f, "missing parameter: {}", field),
+            CrcError::InvalidField(field) =>
+                write!(f, "invalid value of parameter {}", field),
+            CrcError::DuplicateField(field) =>
+                write!(f, "duplicate parameter: {}", field),
+            CrcError::Mismatch { field, expected, actual } =>
+                write!(f, "the listed {} ({:?}) differs from the computed one ({:?})", field, expected, actual),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ValueType> ::std::error::Error for CrcError<T> {}
+
+/// The error type for [`CrcTable::from_parts`](struct.CrcTable.html#method.from_parts):
+/// the lookup table doesn't match the parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableMismatch {
+    /// The first byte whose entry in the table is wrong.
+    pub index: u8
+}
+
+impl fmt::Display for TableMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the entry for {:#04x} doesn't match the parameters", self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for TableMismatch {}
@@ -0,0 +1,171 @@
+use ::{CrcSpec, CrcHasher, CrcBitInput};
+use super::{ValueType, CrcTable, CrcError, Table};
+use core::borrow::Borrow;
+
+
+/// A spec whose width `W` is a part of its type, which lets the compiler
+/// turn the shifts and masks in `update` and `finish` into constants.
+///
+/// Otherwise, this is the same as a `CrcTable` created with `new_with_width(W, ...)`.
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// let mut hasher = CrcFixedTableHasher::from(Crc24::new(0x864CFB, 0xB704CE, false, false, 0));
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), 0x21CF02);
+/// ```
+pub struct CrcFixedTable<T, const W: usize> {
+    spec: CrcTable<T>
+}
+
+/// A 12-bit spec backed by `u16`.
+pub type Crc12 = CrcFixedTable<u16, 12>;
+/// A 24-bit spec backed by `u32`.
+pub type Crc24 = CrcFixedTable<u32, 24>;
+/// An 82-bit spec backed by `u128`.
+pub type Crc82 = CrcFixedTable<u128, 82>;
+
+impl<T: ValueType, const W: usize> CrcFixedTable<T, W> {
+
+    /// The constructor method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    pub fn new(poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Self {
+        CrcFixedTable { spec: CrcTable::new_with_width(W, poly, init, refin, refout, xorout) }
+    }
+
+    /// The fallible constructor method. Fails in the same cases as
+    /// [`CrcTable::try_new_with_width`](struct.CrcTable.html#method.try_new_with_width),
+    /// in particular if `W` is greater than the bit size of `T`.
+    pub fn try_new(poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Result<Self, CrcError<T>> {
+        CrcTable::try_new_with_width(W, poly, init, refin, refout, xorout).map(|spec| CrcFixedTable { spec })
+    }
+
+    /// Returns the equivalent spec with a run-time width.
+    pub fn spec(&self) -> &CrcTable<T> {
+        &self.spec
+    }
+
+    /// Returns the lookup table used by this spec.
+    pub fn table(&self) -> &Table<T> {
+        self.spec.table()
+    }
+
+    /// Returns the initial value of a CRC register,
+    /// taking into account this spec's `refin` value.
+    pub fn start(&self) -> T {
+        self.spec.start()
+    }
+
+    /// Updates a CRC register with one byte of user data,
+    /// taking into account this spec's `refin` value.
+    pub fn update(&self, value: T, byte: u8) -> T {
+        self.spec.update_with_width(W, value, byte)
+    }
+
+    /// Applies the REFOUT and XOROUT stages to the supplied CRC register value,
+    /// returning the resulting checksum.
+    pub fn finish(&self, value: T) -> T {
+        self.spec.finish_with_width(W, value)
+    }
+
+}
+
+impl<T: ValueType, const W: usize> CrcSpec<T> for CrcFixedTable<T, W> {
+    fn width(&self) -> usize { W }
+    fn poly(&self) -> T { self.spec.poly() }
+    fn init(&self) -> T { self.spec.init() }
+    fn refin(&self) -> bool { self.spec.refin() }
+    fn refout(&self) -> bool { self.spec.refout() }
+    fn xorout(&self) -> T { self.spec.xorout() }
+}
+
+
+/// An implementation of `CrcHasher` backed by a `CrcFixedTable`.
+pub struct CrcFixedTableHasher<T, S: Borrow<CrcFixedTable<T, W>>, const W: usize> {
+    value: T,
+    spec: S
+}
+
+impl<T: ValueType, S: Borrow<CrcFixedTable<T, W>>, const W: usize> CrcHasher<T> for CrcFixedTableHasher<T, S, W> {
+    fn reset(&mut self) {
+        self.value = self.spec.borrow().start();
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.value = self.spec.borrow().update(self.value, byte);
+    }
+
+    fn finish(&self) -> T {
+        self.spec.borrow().finish(self.value)
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcFixedTable<T, W>>, const W: usize> CrcBitInput<T> for CrcFixedTableHasher<T, S, W> {
+    fn update_bit(&mut self, bit: bool) {
+        self.value = self.spec.borrow().spec().update_bits(self.value, bit as u8, 1);
+    }
+
+    fn update_last_bits(&mut self, byte: u8, nbits: u8) {
+        self.value = self.spec.borrow().spec().update_last_bits(self.value, byte, nbits);
+    }
+
+    fn refin(&self) -> bool {
+        self.spec.borrow().refin()
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcFixedTable<T, W>>, const W: usize> From<S> for CrcFixedTableHasher<T, S, W> {
+    /// Constructs and returns a hasher from anything that can provide a reference to a spec.
+    fn from(spec_ref: S) -> Self {
+        let mut hasher = CrcFixedTableHasher { value: T::from(0), spec: spec_ref };
+        hasher.reset();
+        hasher
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::{CrcSpec, CrcHasher};
+    use super::super::{CrcError, CrcTable, CrcTableHasher};
+    use super::{CrcFixedTable, CrcFixedTableHasher, Crc12, Crc24, Crc82};
+
+    fn check<H: CrcHasher<T>, T>(mut hasher: H) -> T {
+        hasher.update_from_slice(b"123456789");
+        hasher.finish()
+    }
+
+    #[test]
+    fn crc12() {
+        assert_eq!(check(CrcFixedTableHasher::from(Crc12::new(0x80F, 0, false, true, 0))), 0xDAF);
+    }
+
+    #[test]
+    fn crc24() {
+        assert_eq!(check(CrcFixedTableHasher::from(Crc24::new(0x864CFB, 0xB704CE, false, false, 0))), 0x21CF02);
+    }
+
+    #[test]
+    fn crc82() {
+        let spec = Crc82::new(0x0308C0111011401440411, 0, true, true, 0);
+        assert_eq!(check(CrcFixedTableHasher::from(spec)), 0x09EA83F625023801FD612);
+    }
+
+    #[test]
+    fn matches_run_time_width() {
+        let fixed = CrcFixedTable::<u32, 24>::new(0x5D6DCB, 0xFEDCBA, true, true, 0);
+        let dynamic = CrcTable::new_with_width(24, 0x5D6DCB, 0xFEDCBA, true, true, 0);
+        assert_eq!(fixed.width(), 24);
+        assert_eq!(&fixed.table()[..], &dynamic.table()[..]);
+        assert_eq!(check(CrcFixedTableHasher::from(&fixed)), check(CrcTableHasher::from(&dynamic)));
+    }
+
+    #[test]
+    fn width_too_large() {
+        assert_eq!(CrcFixedTable::<u16, 17>::try_new(1, 0, false, false, 0).err(), Some(CrcError::UnsupportedWidth(17)));
+    }
+}
@@ -0,0 +1,141 @@
+use ::CrcSpec;
+use super::{ValueType, CrcTable, Table};
+use core::borrow::Borrow;
+use core::fmt;
+
+
+/// Prints the parameters of the algorithm, omitting the lookup table.
+/// The values are printed in hexadecimal.
+impl<T: ValueType, B: Borrow<Table<T>>> fmt::Debug for CrcTable<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.width();
+        f.debug_struct("CrcTable")
+            .field("width", &width)
+            .field("poly", &Hex(self.poly(), width))
+            .field("init", &Hex(self.init(), width))
+            .field("refin", &self.refin())
+            .field("refout", &self.refout())
+            .field("xorout", &Hex(self.xorout(), width))
+            .finish()
+    }
+}
+
+/// Prints the algorithm in the format of the
+/// [CRC RevEng catalogue](http://reveng.sourceforge.net/crc-catalogue/all.htm),
+/// including the check value and the residue. The result can be parsed back with `FromStr`.
+///
+/// ```
+/// use crc_rocksoft::primitive::CrcTable;
+///
+/// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+/// assert_eq!(
+///     spec.to_string(),
+///     "width=32 poly=0x04c11db7 init=0xffffffff refin=true refout=true xorout=0xffffffff check=0xcbf43926 residue=0xdebb20e3"
+/// );
+/// ```
+impl<T: ValueType, B: Borrow<Table<T>>> fmt::Display for CrcTable<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.width();
+        write!(
+            f,
+            "width={} poly={} init={} refin={} refout={} xorout={} check={} residue={}",
+            width,
+            Hex(self.poly(), width),
+            Hex(self.init(), width),
+            self.refin(),
+            self.refout(),
+            Hex(self.xorout(), width),
+            Hex(self.check(), width),
+            Hex(self.residue(), width),
+        )
+    }
+}
+
+/// A value printed as `0x` followed by as many lowercase hex digits as `width` bits need.
+pub(crate) struct Hex<T>(pub T, pub usize);
+
+impl<T: ValueType> fmt::Display for Hex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Hex(value, width) = *self;
+        let bytes = value.be_bytes(width);
+        let skip = bytes.len() * 2 - width.div_ceil(4);
+        f.write_str("0x")?;
+        for (i, b) in bytes.iter().enumerate() {
+            if i > 0 || skip == 0 {
+                write!(f, "{:02x}", b)?;
+            } else {
+                write!(f, "{:x}", b & 0x0F)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: ValueType> fmt::Debug for Hex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+    use super::super::{CrcTable, Wide};
+
+    #[test]
+    fn debug() {
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0u32);
+        assert_eq!(
+            format!("{:?}", spec),
+            "CrcTable { width: 32, poly: 0x04c11db7, init: 0xffffffff, refin: true, refout: false, xorout: 0x00000000 }"
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16).to_string(),
+            "width=16 poly=0x8005 init=0xffff refin=true refout=true xorout=0x0000 check=0x4b37 residue=0x0000"
+        );
+    }
+
+    #[test]
+    fn odd_widths() {
+        assert_eq!(
+            CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8).to_string(),
+            "width=5 poly=0x05 init=0x1f refin=true refout=true xorout=0x1f check=0x19 residue=0x06"
+        );
+        assert_eq!(
+            CrcTable::new_with_width(3, 0x3u8, 0u8, false, false, 0x7u8).to_string(),
+            "width=3 poly=0x3 init=0x0 refin=false refout=false xorout=0x7 check=0x4 residue=0x2"
+        );
+        assert_eq!(
+            CrcTable::new_with_width(15, 0x6815u16, 0u16, false, false, 1u16).to_string(),
+            "width=15 poly=0x6815 init=0x0000 refin=false refout=false xorout=0x0001 check=0x2566 residue=0x6815"
+        );
+        assert_eq!(
+            CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128).to_string(),
+            "width=82 poly=0x0308c0111011401440411 init=0x000000000000000000000 refin=true refout=true \
+             xorout=0x000000000000000000000 check=0x09ea83f625023801fd612 residue=0x000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn wide() {
+        let spec = CrcTable::new_with_width(12, Wide([0x08, 0x0F]), Wide([0, 0]), false, true, Wide([0, 0]));
+        assert_eq!(format!("{:?}", spec), "CrcTable { width: 12, poly: 0x80f, init: 0x000, refin: false, refout: true, xorout: 0x000 }");
+    }
+
+    #[test]
+    fn round_trip() {
+        for spec in &[
+            CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32),
+            CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32),
+            CrcTable::new_with_width(5, 0x05u32, 0x1Fu32, true, true, 0x1Fu32),
+        ] {
+            let parsed: CrcTable<u32> = spec.to_string().parse().unwrap();
+            assert_eq!(parsed.to_string(), spec.to_string());
+        }
+    }
+}
@@ -1,21 +1,28 @@
-use ::{CrcSpec, CrcHasher};
+use ::{CrcSpec, CrcHasher, CrcBitInput};
 use super::*;
-use std::borrow::Borrow;
+use core::borrow::Borrow;
+use core::hash::Hasher;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")] use alloc::sync::Arc;
 
 
 /// An implementation of `CrcHasher` that has a way to immutably borrow
 /// a reference to its `CrcSpec`. The borrowing mechanism is abstracted.
 ///
 /// Instances can be obtained via the `From` mechanism (see below).
-pub struct CrcTableHasher<T, S: Borrow<CrcTable<T>>> {
+/// `B` is the type of the spec's lookup table, which is only different from the default
+/// for specs that borrow a static table; see [`CrcTable::from_parts`](struct.CrcTable.html#method.from_parts).
+#[derive(Clone)]
+pub struct CrcTableHasher<T, S: Borrow<CrcTable<T, B>>, B = Table<T>> {
     value: T,
-    spec: S
+    spec: S,
+    table: PhantomData<B>
 }
 
 
-impl<T: ValueType, S: Borrow<CrcTable<T>>> CrcHasher<T> for CrcTableHasher<T, S> {
+impl<T: ValueType, S: Borrow<CrcTable<T, B>>, B: Borrow<Table<T>>> CrcHasher<T> for CrcTableHasher<T, S, B> {
     fn reset(&mut self) {
-        self.value = self.spec.borrow().init();
+        self.value = self.spec.borrow().start();
     }
 
     fn update(&mut self, byte: u8) {
@@ -25,13 +32,108 @@ impl<T: ValueType, S: Borrow<CrcTable<T>>> CrcHasher<T> for CrcTableHasher<T, S>
     fn finish(&self) -> T {
         self.spec.borrow().finish(self.value)
     }
+
+    fn update_from_slice(&mut self, bytes: &[u8]) {
+        self.value = self.spec.borrow().checksum_with_state(self.value, bytes);
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcTable<T, B>>, B: Borrow<Table<T>>> CrcBitInput<T> for CrcTableHasher<T, S, B> {
+    fn update_bit(&mut self, bit: bool) {
+        self.value = self.spec.borrow().update_bits(self.value, bit as u8, 1);
+    }
+
+    fn update_last_bits(&mut self, byte: u8, nbits: u8) {
+        self.value = self.spec.borrow().update_last_bits(self.value, byte, nbits);
+    }
+
+    fn refin(&self) -> bool {
+        self.spec.borrow().refin()
+    }
 }
 
 
-impl<T: ValueType, S: Borrow<CrcTable<T>>> From<S> for CrcTableHasher<T, S> {
+impl<T: ValueType, S: Borrow<CrcTable<T, B>>, B: Borrow<Table<T>>> CrcTableHasher<T, S, B> {
+    /// Returns the spec used by this hasher.
+    pub fn spec(&self) -> &CrcTable<T, B> {
+        self.spec.borrow()
+    }
+
+    /// Returns the current value of the register, before the final stages of the algorithm
+    /// that `finish` applies. This is the same value that
+    /// [`CrcTable::checksum_with_state`](struct.CrcTable.html#method.checksum_with_state)
+    /// works with, so it's reflected if `refin` is set, and it starts as
+    /// [`CrcTable::start`](struct.CrcTable.html#method.start).
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// let mut hasher = CrcTableHasher::from(&spec);
+    /// hasher.update_from_slice(b"1234");
+    /// let checkpoint = hasher.peek_state();
+    /// assert_eq!(checkpoint, spec.checksum_with_state(spec.start(), b"1234"));
+    ///
+    /// let mut resumed = CrcTableHasher::from(&spec);
+    /// resumed.set_state(checkpoint);
+    /// resumed.update_from_slice(b"56789");
+    /// assert_eq!(resumed.finish(), 0xCBF43926);
+    /// ```
+    pub fn peek_state(&self) -> T {
+        self.value
+    }
+
+    /// Replaces the value of the register, e. g. with one saved by
+    /// [`peek_state`](#method.peek_state) to resume a computation. The value isn't checked;
+    /// one from a hasher with a different spec makes the checksum meaningless.
+    pub fn set_state(&mut self, value: T) {
+        self.value = value;
+    }
+
+    /// Returns the register for a checkpoint of a long computation, the same as
+    /// [`peek_state`](#method.peek_state). Saved along with the number of bytes processed
+    /// so far, it's all that's needed to resume with [`from_state`](#method.from_state),
+    /// e. g. after a crash.
+    pub fn state(&self) -> T {
+        self.peek_state()
+    }
+
+    /// Constructs a hasher that resumes a computation from a register returned
+    /// by [`state`](#method.state).
+    ///
+    /// The spec must be the same as that of the hasher whose state was saved; the register
+    /// carries no trace of it, so a different spec is not detected, and makes the checksum
+    /// meaningless. The spec doesn't have to be the same object, though, so the state
+    /// can be restored in another process.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// let mut hasher = CrcTableHasher::from(&spec);
+    /// hasher.update_from_slice(b"12345");
+    /// let (state, offset) = (hasher.state(), 5);
+    ///
+    /// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// let mut resumed = CrcTableHasher::from_state(&spec, state);
+    /// resumed.update_from_slice(&b"123456789"[offset..]);
+    /// assert_eq!(resumed.finish(), 0xCBF43926);
+    /// ```
+    pub fn from_state(spec: S, state: T) -> Self {
+        CrcTableHasher { value: state, spec, table: PhantomData }
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcTable<T, B>>, B: Borrow<Table<T>>> From<S> for CrcTableHasher<T, S, B> {
     /// Constructs and returns a hasher from anything that can provide a reference to a spec:
     /// from encapsulating a spec inside the hasher, to using a reference, a Box or any other option.
     ///
+    /// Encapsulating a spec moves its whole lookup table into the hasher. A spec whose table
+    /// is on the heap, constructed with [`CrcTable::new_boxed`](struct.CrcTable.html#method.new_boxed),
+    /// is the cheap way to have a hasher own its spec: only the pointer to the table is moved.
+    ///
     /// ```
     /// use crc_rocksoft::*;
     /// use crc_rocksoft::primitive::*;
@@ -42,23 +144,107 @@ impl<T: ValueType, S: Borrow<CrcTable<T>>> From<S> for CrcTableHasher<T, S> {
     /// let spec_ref = &spec;
     /// let referenced = CrcTableHasher::from(spec_ref);
     ///
-    /// let spec_box = Box::new(CrcTable::new(0x04C11DB7u32, 0u32, false, false, 0xFFFFFFFFu32));
+    /// let spec_box = CrcTable::new_boxed(0x04C11DB7u32, 0u32, false, false, 0xFFFFFFFFu32);
     /// let boxed = CrcTableHasher::from(spec_box);
     /// ```
     fn from(spec_ref: S) -> Self {
-        let mut hasher = CrcTableHasher { value: T::from(0), spec: spec_ref };
+        let mut hasher = CrcTableHasher { value: T::from(0), spec: spec_ref, table: PhantomData };
         hasher.reset();
         hasher
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T: ValueType> CrcTable<T> {
+    /// Moves the spec into an `Arc`, so that it can be shared between threads,
+    /// each of them creating its own hashers with
+    /// [`CrcTableHasher::from_arc`](struct.CrcTableHasher.html#method.from_arc).
+    ///
+    /// Only the spec, i. e. the parameters and the lookup table, is shared.
+    /// The spec is immutable; the register value lives in each hasher.
+    ///
+    /// Only available with the `alloc` feature.
+    ///
+    /// ```
+    /// use std::thread;
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).shared();
+    /// let threads: Vec<_> = (0..4).map(|_| {
+    ///     let mut hasher = CrcTableHasher::from_arc(&spec);
+    ///     thread::spawn(move || {
+    ///         hasher.update_from_slice(b"123456789");
+    ///         hasher.finish()
+    ///     })
+    /// }).collect();
+    /// for thread in threads {
+    ///     assert_eq!(thread.join().unwrap(), 0xCBF43926);
+    /// }
+    /// ```
+    pub fn shared(self) -> Arc<CrcTable<T>> {
+        Arc::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ValueType> CrcTableHasher<T, Arc<CrcTable<T>>> {
+    /// Constructs a hasher that holds another reference to a shared spec.
+    /// Creating it is cheap: the lookup table isn't copied.
+    ///
+    /// Only available with the `alloc` feature.
+    pub fn from_arc(spec: &Arc<CrcTable<T>>) -> Self {
+        CrcTableHasher::from(Arc::clone(spec))
+    }
+}
+
+/// Allows using a 64-bit CRC wherever a `core::hash::Hasher` is expected.
+///
+/// Note that both `Hasher` and `CrcHasher` declare a `finish` method;
+/// if both traits are in scope, you'll need to disambiguate the calls.
+impl<S: Borrow<CrcTable<u64>>> Hasher for CrcTableHasher<u64, S> {
+    fn finish(&self) -> u64 {
+        CrcHasher::finish(self)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update_from_slice(bytes);
+    }
+}
+
+
+/// A wrapper that allows using a CRC narrower than 64 bits as a `core::hash::Hasher`.
+/// The checksum is zero-extended to `u64`.
+///
+/// ```
+/// use std::hash::Hasher;
+/// use crc_rocksoft::primitive::*;
+///
+/// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+/// let mut hasher = ZeroExtended(CrcTableHasher::from(spec));
+/// hasher.write(b"123456789");
+/// assert_eq!(hasher.finish(), 0xCBF43926u64);
+/// ```
+pub struct ZeroExtended<H>(pub H);
+
+impl<T: ValueType + Into<u64>, S: Borrow<CrcTable<T>>> Hasher for ZeroExtended<CrcTableHasher<T, S>> {
+    fn finish(&self) -> u64 {
+        CrcHasher::finish(&self.0).into()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update_from_slice(bytes);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::vec::Vec;
     use ::CrcHasher;
-    use super::super::{ValueType, CrcTable};
+    use super::super::{ValueType, CrcTable, Wide};
     use super::CrcTableHasher;
 
-    fn feed<T: From<u8>>(hasher: &mut CrcHasher<T>) -> &mut CrcHasher<T> {
+    fn feed<T: From<u8>>(hasher: &mut dyn CrcHasher<T>) -> &mut dyn CrcHasher<T> {
         for i in 1..10 {
             hasher.update(0x30 + i);
         }
@@ -87,8 +273,319 @@ mod tests {
         assert_eq!(h.finish(), 0xCBF43926u32);
     }
 
+    #[test]
+    fn peek_and_set_state() {
+        use ::CrcSpec;
+        for &(refin, refout) in &[(false, false), (true, true), (true, false)] {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, refin, refout, 0x123456);
+            let mut whole = CrcTableHasher::from(&spec);
+            whole.update_from_slice(b"123456789");
+            let mut first = CrcTableHasher::from(&spec);
+            assert_eq!(first.peek_state(), spec.start());
+            for (i, &byte) in b"123456789".iter().enumerate() {
+                first.update(byte);
+                let mut resumed = CrcTableHasher::from(&spec);
+                resumed.set_state(first.peek_state());
+                resumed.update_from_slice(&b"123456789"[i + 1..]);
+                assert_eq!(resumed.finish(), whole.finish(), "refin: {}, split at {}", spec.refin(), i + 1);
+            }
+            assert_eq!(first.peek_state(), whole.peek_state());
+            first.reset();
+            assert_eq!(first.peek_state(), spec.start());
+        }
+    }
+
+    #[test]
+    fn checkpoints() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 + (i >> 3)) as u8).collect();
+        let spec = CrcTable::new(0x42F0E1EBA9EA3693u64, !0, true, true, !0);
+        let mut whole = CrcTableHasher::from(&spec);
+        whole.update_from_slice(&data);
+
+        // Save a checkpoint every 100 bytes, and resume from each one with a new spec.
+        let mut hasher = CrcTableHasher::from(&spec);
+        let mut checkpoints = Vec::new();
+        for (i, chunk) in data.chunks(100).enumerate() {
+            hasher.update_from_slice(chunk);
+            checkpoints.push((hasher.state(), (i + 1) * 100));
+        }
+        assert_eq!(hasher.state(), hasher.peek_state());
+        for &(state, offset) in &checkpoints {
+            let mut resumed = CrcTableHasher::from_state(spec.clone(), state);
+            resumed.update_from_slice(&data[offset..]);
+            assert_eq!(resumed.finish(), whole.finish(), "offset: {}", offset);
+        }
+
+        // The tail hashed separately is combined with the checksum at the checkpoint.
+        let (state, offset) = checkpoints[3];
+        let mut tail = CrcTableHasher::from(&spec);
+        tail.update_from_slice(&data[offset..]);
+        let head = CrcTableHasher::from_state(&spec, state).finish();
+        assert_eq!(spec.combine(head, tail.finish(), data.len() - offset), whole.finish());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shared() {
+        use std::sync::Arc;
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).shared();
+        let mut a = CrcTableHasher::from_arc(&spec);
+        let mut b = CrcTableHasher::from_arc(&spec);
+        assert_eq!(Arc::strong_count(&spec), 3);
+        a.update_from_slice(b"123456789");
+        b.update_from_slice(b"1234");
+        assert_eq!(a.finish(), 0xCBF43926);
+        b.update_from_slice(b"56789");
+        assert_eq!(b.finish(), 0xCBF43926);
+        drop(a);
+        assert_eq!(Arc::strong_count(&spec), 2);
+    }
+
+    #[test]
+    fn clone() {
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        let mut h = CrcTableHasher::from(&spec);
+        h.update_from_slice(b"12345");
+        let mut cloned = h.clone();
+        cloned.update_from_slice(b"6789");
+        assert_eq!(cloned.finish(), 0xCBF43926u32);
+        h.update_from_slice(b"6789");
+        assert_eq!(h.finish(), 0xCBF43926u32);
+
+        let mut owned = CrcTableHasher::from(spec);
+        owned.update_from_slice(b"12345");
+        let mut cloned = owned.clone();
+        owned.update_from_slice(b"6789");
+        assert_eq!(owned.finish(), 0xCBF43926u32);
+        cloned.update_from_slice(b"6789");
+        assert_eq!(cloned.finish(), 0xCBF43926u32);
+    }
+
+    #[cfg(feature = "std")]
+    mod update_from_reader {
+        use std::io::{self, Read};
+        use std::vec::Vec;
+        use ::CrcHasher;
+        use super::super::CrcTableHasher;
+        use primitive::CrcTable;
+
+        /// A reader that returns at most `chunk` bytes per call,
+        /// and fails with `Interrupted` before every chunk.
+        struct Choppy<'a> {
+            data: &'a [u8],
+            chunk: usize,
+            interrupt: bool,
+        }
+
+        impl<'a> Read for Choppy<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.interrupt = !self.interrupt;
+                if self.interrupt {
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+                }
+                let n = self.chunk.min(buf.len()).min(self.data.len());
+                buf[..n].copy_from_slice(&self.data[..n]);
+                self.data = &self.data[n..];
+                Ok(n)
+            }
+        }
+
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+
+        fn spec() -> CrcTable<u32> {
+            CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32)
+        }
+
+        #[test]
+        fn large_input() {
+            let data: Vec<u8> = (0..100_000u32).map(|i| (i * 31 + i / 256) as u8).collect();
+            let mut expected = CrcTableHasher::from(spec());
+            expected.update_from_slice(&data);
+
+            let mut h = CrcTableHasher::from(spec());
+            assert_eq!(h.update_from_reader(&mut io::Cursor::new(&data)).unwrap(), data.len() as u64);
+            assert_eq!(h.finish(), expected.finish());
+        }
+
+        #[test]
+        fn short_reads_and_interruptions() {
+            let mut h = CrcTableHasher::from(spec());
+            let mut reader = Choppy { data: b"123456789", chunk: 2, interrupt: false };
+            assert_eq!(h.update_from_reader(&mut reader).unwrap(), 9);
+            assert_eq!(h.finish(), 0xCBF43926);
+        }
+
+        #[test]
+        fn continues_the_message() {
+            let mut h = CrcTableHasher::from(spec());
+            h.update_from_slice(b"1234");
+            assert_eq!(h.update_from_reader(&mut &b"56789"[..]).unwrap(), 5);
+            assert_eq!(h.finish(), 0xCBF43926);
+        }
+
+        #[test]
+        fn error() {
+            let mut h = CrcTableHasher::from(spec());
+            assert_eq!(h.update_from_reader(&mut Broken).unwrap_err().kind(), io::ErrorKind::Other);
+        }
+    }
+
     #[test]
     fn crc32_posix() {
         test(CrcTable::new(0x04C11DB7u32, 0u32, false, false, 0xFFFFFFFFu32), 0x765E7680u32);
     }
+
+    #[test]
+    fn crc3_gsm() {
+        test(CrcTable::new_with_width(3, 0x3u8, 0u8, false, false, 0x7u8), 0x4u8);
+    }
+
+    #[test]
+    fn crc4_g_704() {
+        test(CrcTable::new_with_width(4, 0x3u8, 0u8, true, true, 0u8), 0x7u8);
+    }
+
+    #[test]
+    fn crc5_usb() {
+        test(CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8), 0x19u8);
+    }
+
+    #[test]
+    fn crc7_mmc() {
+        test(CrcTable::new_with_width(7, 0x09u8, 0u8, false, false, 0u8), 0x75u8);
+    }
+
+    #[test]
+    fn crc5_in_u32() {
+        test(CrcTable::new_with_width(5, 0x05u32, 0x1Fu32, true, true, 0x1Fu32), 0x19u32);
+    }
+
+    #[test]
+    fn crc8_smbus() {
+        test(CrcTable::new(0x07u8, 0u8, false, false, 0u8), 0xF4u8);
+    }
+
+    #[test]
+    fn crc8_maxim() {
+        test(CrcTable::new(0x31u8, 0u8, true, true, 0u8), 0xA1u8);
+    }
+
+    #[test]
+    fn crc8_cdma2000() {
+        test(CrcTable::new(0x9Bu8, 0xFFu8, false, false, 0u8), 0xDAu8);
+    }
+
+    #[test]
+    fn crc16_ccitt_false() {
+        test(CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16), 0x29B1u16);
+    }
+
+    #[test]
+    fn crc16_iso_iec_14443_3_a() {
+        // A reflected algorithm with an asymmetric init value
+        test(CrcTable::new(0x1021u16, 0xC6C6u16, true, true, 0u16), 0xBF05u16);
+    }
+
+    #[test]
+    fn crc32_bzip2() {
+        test(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32), 0xFC891918u32);
+    }
+
+    #[test]
+    fn crc64_ecma_182() {
+        test(CrcTable::new(0x42F0E1EBA9EA3693u64, 0u64, false, false, 0u64), 0x6C40DF5F0B497347u64);
+    }
+
+    #[test]
+    fn crc64_we() {
+        test(CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, false, false, !0u64), 0x62EC59E3F1A4F00Au64);
+    }
+
+    // The check values for mismatched refin/refout other than CRC-12/3GPP aren't published,
+    // so they were computed with an independent bitwise implementation of the model.
+
+    #[test]
+    fn crc32_refin_only() {
+        test(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0xFFFFFFFFu32), 0x649C2FD3u32);
+    }
+
+    #[test]
+    fn crc32_refout_only() {
+        test(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, true, 0xFFFFFFFFu32), 0x1898913Fu32);
+    }
+
+    #[test]
+    fn crc16_refin_only() {
+        test(CrcTable::new(0x8005u16, 0u16, true, false, 0u16), 0xBCDDu16);
+    }
+
+    #[test]
+    fn crc16_refout_only() {
+        test(CrcTable::new(0x8005u16, 0u16, false, true, 0u16), 0x177Fu16);
+    }
+
+    #[test]
+    fn crc82_darc() {
+        test(
+            CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128),
+            0x09EA83F625023801FD612u128
+        );
+    }
+
+    #[test]
+    fn crc82_darc_wide() {
+        let wide = |value: u128| {
+            let mut bytes = [0; 11];
+            bytes.copy_from_slice(&value.to_be_bytes()[5..]);
+            Wide(bytes)
+        };
+        test(
+            CrcTable::new_with_width(82, wide(0x0308C0111011401440411), wide(0), true, true, wide(0)),
+            wide(0x09EA83F625023801FD612)
+        );
+    }
+
+    #[test]
+    fn std_hasher() {
+        use std::hash::Hasher;
+        let mut h = CrcTableHasher::from(CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64));
+        h.write(b"12345");
+        h.write(b"6789");
+        assert_eq!(Hasher::finish(&h), 0x995DC9BBDF1939FAu64);
+    }
+
+    #[test]
+    fn std_hasher_zero_extended() {
+        use std::hash::Hasher;
+        use super::ZeroExtended;
+        let mut h = ZeroExtended(CrcTableHasher::from(CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16)));
+        h.write(b"123456789");
+        assert_eq!(h.finish(), 0x29B1u64);
+    }
+
+    #[test]
+    fn crc12_3gpp() {
+        test(CrcTable::new_with_width(12, 0x80Fu16, 0u16, false, true, 0u16), 0xDAFu16);
+    }
+
+    #[test]
+    fn crc10_atm() {
+        test(CrcTable::new_with_width(10, 0x233u16, 0u16, false, false, 0u16), 0x199u16);
+    }
+
+    #[test]
+    fn crc24_ble() {
+        test(CrcTable::new_with_width(24, 0x00065Bu32, 0x555555u32, true, true, 0u32), 0xC25A56u32);
+    }
+
+    #[test]
+    fn crc24_openpgp() {
+        test(CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32), 0x21CF02u32);
+    }
 }
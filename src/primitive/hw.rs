@@ -0,0 +1,338 @@
+//! Hardware-accelerated computation of specific algorithms.
+//! Only available with the `hw` feature.
+//!
+//! The CPU features are detected at run time, so a binary built for a generic target
+//! uses the instructions wherever they're available, and the lookup table elsewhere.
+
+use ::CrcSpec;
+use super::{ValueType, CrcTable, Table};
+use core::borrow::Borrow;
+
+
+/// The poly of CRC-32C (Castagnoli), which is the one computed by the SSE4.2 `crc32` instruction
+/// and by the ARMv8 `crc32c` instructions.
+const CASTAGNOLI: u32 = 0x1EDC6F41;
+
+/// The poly of CRC-32 (ISO-HDLC), which is computed by the ARMv8 `crc32` instructions,
+/// and by carry-less multiplication on x86-64.
+const ISO_HDLC: u32 = 0x04C11DB7;
+
+/// Updates the register `value` of `spec` with all the bytes of `data` using the CPU's
+/// CRC instructions, if the algorithm is supported by them and by the CPU.
+/// Returns `None` otherwise, in which case the caller has to use the lookup table.
+///
+/// The register is the same as that of `CrcTable`, so the result can be mixed
+/// with the table-driven updates.
+pub(super) fn checksum_with_state<T: ValueType, B: Borrow<Table<T>>>(spec: &CrcTable<T, B>, value: T, data: &[u8]) -> Option<T> {
+    if !spec.refin() || spec.width() != 32 {
+        return None;
+    }
+    match to_u32(spec.poly()) {
+        CASTAGNOLI => crc32c(to_u32(value), data).map(from_u32),
+        ISO_HDLC => {
+            if let Some(value) = crc32(to_u32(value), data) {
+                return Some(from_u32(value));
+            }
+            // The folding only handles whole 16-byte blocks; the rest is left to the table.
+            let folded = data.len() & !15;
+            crc32_clmul(to_u32(value), &data[..folded])
+                .map(|value| data[folded..].iter().fold(from_u32(value), |value, &b| spec.update(value, b)))
+        }
+        _ => None
+    }
+}
+
+/// Returns the register of CRC-32C after processing `data`,
+/// or `None` if the CPU doesn't have the `crc32` instruction.
+fn crc32c(value: u32, data: &[u8]) -> Option<u32> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse4.2") {
+            // Safe, since the CPU has just been checked for the instructions.
+            return Some(unsafe { crc32c_sse42(value, data) });
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if is_aarch64_feature_detected!("crc") {
+            // Safe, since the CPU has just been checked for the instructions.
+            return Some(unsafe { crc32_arm(value, data, true) });
+        }
+    }
+    let _ = (value, data);
+    None
+}
+
+/// Returns the register of the reflected CRC-32 after processing `data`,
+/// or `None` if the CPU doesn't have instructions for it. Only ARMv8 has them;
+/// see `crc32_clmul` for x86-64.
+fn crc32(value: u32, data: &[u8]) -> Option<u32> {
+    #[cfg(target_arch = "aarch64")]
+    {
+        if is_aarch64_feature_detected!("crc") {
+            // Safe, since the CPU has just been checked for the instructions.
+            return Some(unsafe { crc32_arm(value, data, false) });
+        }
+    }
+    let _ = (value, data);
+    None
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.2")]
+unsafe fn crc32c_sse42(value: u32, data: &[u8]) -> u32 {
+    use core::arch::x86_64::{_mm_crc32_u64, _mm_crc32_u8};
+    let mut words = data.chunks_exact(8);
+    let mut value = u64::from(value);
+    for word in &mut words {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(word);
+        value = _mm_crc32_u64(value, u64::from_le_bytes(bytes));
+    }
+    words.remainder().iter().fold(value as u32, |value, &b| _mm_crc32_u8(value, b))
+}
+
+/// Computes CRC-32C if `castagnoli` is set, and CRC-32 otherwise.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "crc")]
+unsafe fn crc32_arm(value: u32, data: &[u8], castagnoli: bool) -> u32 {
+    use core::arch::aarch64::{__crc32b, __crc32cb, __crc32cd, __crc32d};
+    let mut words = data.chunks_exact(8);
+    let mut value = value;
+    for word in &mut words {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(word);
+        let word = u64::from_le_bytes(bytes);
+        value = if castagnoli { __crc32cd(value, word) } else { __crc32d(value, word) };
+    }
+    words.remainder().iter()
+        .fold(value, |value, &b| if castagnoli { __crc32cb(value, b) } else { __crc32b(value, b) })
+}
+
+/// Returns the register of the reflected CRC-32 after processing `data`, whose length
+/// is a multiple of 16, or `None` if the CPU doesn't have the `pclmulqdq` instruction
+/// or `data` is too short for the folding to pay off.
+fn crc32_clmul(value: u32, data: &[u8]) -> Option<u32> {
+    debug_assert_eq!(data.len() % 16, 0);
+    #[cfg(target_arch = "x86_64")]
+    {
+        if data.len() >= 64 && is_x86_feature_detected!("pclmulqdq") {
+            // Safe, since the CPU has just been checked for the instructions.
+            return Some(unsafe { crc32_pclmulqdq(value, data) });
+        }
+    }
+    let _ = (value, data);
+    None
+}
+
+/// The constants of the folding, for the bit-reflected CRC-32 poly. The exponents
+/// are those of the paper; the 64-bit halves of a block are multiplied separately.
+#[cfg(target_arch = "x86_64")]
+mod clmul {
+    /// x^(4*128+32) mod P and x^(4*128-32) mod P: folding by four blocks.
+    pub const K1: i64 = 0x154442BD4;
+    pub const K2: i64 = 0x1C6E41596;
+    /// x^(128+32) mod P and x^(128-32) mod P: folding by one block.
+    pub const K3: i64 = 0x1751997D0;
+    pub const K4: i64 = 0x0CCAA009E;
+    /// x^64 mod P: reduction from 96 bits to 64.
+    pub const K5: i64 = 0x163CD6124;
+    /// The poly with its top bit, and x^64 / P, for the Barrett reduction.
+    pub const P: i64 = 0x1DB710641;
+    pub const MU: i64 = 0x1F7011641;
+}
+
+/// The folding algorithm from "Fast CRC Computation for Generic Polynomials Using
+/// PCLMULQDQ Instruction" by Gopal et al. (Intel, 2009), in its bit-reflected variant.
+///
+/// The message is a polynomial over GF(2), so a 128-bit block `a` followed by `n` more bits
+/// is congruent to `a_hi * (x^(n+64) mod P) + a_lo * (x^n mod P)`, which is 96 bits wide
+/// and can be XORed into the block `n` bits later. Four blocks are folded 64 bytes ahead
+/// at once while possible, then one block 16 bytes ahead, and the remaining 128 bits
+/// are reduced to 64 and finally to the 32-bit register by Barrett reduction.
+/// The constants are `x^n mod P` for the respective `n`, reflected and shifted by one bit
+/// to make up for the reflected product being one bit short.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq", enable = "sse2")]
+unsafe fn crc32_pclmulqdq(value: u32, data: &[u8]) -> u32 {
+    use core::arch::x86_64::*;
+    use self::clmul::*;
+
+    #[inline]
+    #[target_feature(enable = "pclmulqdq")]
+    unsafe fn fold(a: __m128i, b: __m128i, keys: __m128i) -> __m128i {
+        let lo = _mm_clmulepi64_si128(a, keys, 0x00);
+        let hi = _mm_clmulepi64_si128(a, keys, 0x11);
+        _mm_xor_si128(_mm_xor_si128(b, lo), hi)
+    }
+
+    let mut blocks = data.chunks_exact(16)
+        .map(|block| _mm_loadu_si128(block.as_ptr() as *const __m128i));
+    // The caller ensures at least four blocks.
+    let mut x3 = _mm_xor_si128(blocks.next().unwrap(), _mm_cvtsi32_si128(value as i32));
+    let mut x2 = blocks.next().unwrap();
+    let mut x1 = blocks.next().unwrap();
+    let mut x0 = blocks.next().unwrap();
+
+    let k1k2 = _mm_set_epi64x(K2, K1);
+    let mut remaining = data.len() / 16 - 4;
+    while remaining >= 4 {
+        x3 = fold(x3, blocks.next().unwrap(), k1k2);
+        x2 = fold(x2, blocks.next().unwrap(), k1k2);
+        x1 = fold(x1, blocks.next().unwrap(), k1k2);
+        x0 = fold(x0, blocks.next().unwrap(), k1k2);
+        remaining -= 4;
+    }
+
+    let k3k4 = _mm_set_epi64x(K4, K3);
+    let mut x = fold(x3, x2, k3k4);
+    x = fold(x, x1, k3k4);
+    x = fold(x, x0, k3k4);
+    for block in blocks {
+        x = fold(x, block, k3k4);
+    }
+
+    // 128 bits to 96, then to 64.
+    let low32 = _mm_set_epi32(0, 0, 0, !0);
+    let x = _mm_xor_si128(_mm_clmulepi64_si128(x, k3k4, 0x10), _mm_srli_si128(x, 8));
+    let x = _mm_xor_si128(
+        _mm_clmulepi64_si128(_mm_and_si128(x, low32), _mm_set_epi64x(0, K5), 0x00),
+        _mm_srli_si128(x, 4)
+    );
+
+    // Barrett reduction: the quotient is (x mod x^32) * MU, and the remainder ends up
+    // in the upper half of the low 64 bits, since everything is reflected.
+    let pu = _mm_set_epi64x(MU, P);
+    let t1 = _mm_clmulepi64_si128(_mm_and_si128(x, low32), pu, 0x10);
+    let t2 = _mm_clmulepi64_si128(_mm_and_si128(t1, low32), pu, 0x00);
+    _mm_cvtsi128_si32(_mm_srli_si128(_mm_xor_si128(x, t2), 4)) as u32
+}
+
+/// The low 32 bits of a register value.
+fn to_u32<T: ValueType>(value: T) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&value.le_bytes(32));
+    u32::from_le_bytes(bytes)
+}
+
+fn from_u32<T: ValueType>(value: u32) -> T {
+    value.to_le_bytes().iter().enumerate()
+        .fold(T::from(0), |result, (i, &b)| result ^ (T::from(b) << ((i * 8) as u8)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use primitive::test_util::Random;
+    use super::super::CrcTable;
+    use super::{checksum_with_state, crc32c};
+
+    fn data() -> Vec<u8> {
+        Random(0x2545F4914F6CDD1D).data(1000)
+    }
+
+    fn crc32c_spec() -> CrcTable<u32> {
+        CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32)
+    }
+
+    #[test]
+    fn matches_table() {
+        if crc32c(0, b"").is_none() {
+            // The CPU has no CRC-32C instructions.
+            return;
+        }
+        let data = data();
+        let spec = crc32c_spec();
+        for start in 0..8 {
+            for len in (0..40).chain(Some(data.len() - start)) {
+                let data = &data[start..start + len];
+                let table = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+                assert_eq!(checksum_with_state(&spec, spec.start(), data), Some(table), "start: {}, len: {}", start, len);
+            }
+        }
+        assert_eq!(spec.check(), 0xE3069283);
+        let wide = CrcTable::new_with_width(32, 0x1EDC6F41u64, 0, true, false, 0xFFFFFFFF);
+        let value = checksum_with_state(&wide, 0x12345678, &data);
+        assert_eq!(value, Some(data.iter().fold(0x12345678, |value, &b| wide.update(value, b))));
+    }
+
+    fn crc32_spec() -> CrcTable<u32> {
+        CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32)
+    }
+
+    #[test]
+    fn crc32_matches_table() {
+        let spec = crc32_spec();
+        if checksum_with_state(&spec, 0, &[0; 64]).is_none() {
+            // The CPU has no CRC-32 instructions.
+            return;
+        }
+        let data = data();
+        for &init in &[spec.start(), 0, 0x12345678] {
+            for start in 0..16 {
+                let mut expected = init;
+                for len in 0..=data.len() - start {
+                    let actual = checksum_with_state(&spec, init, &data[start..start + len]);
+                    assert_eq!(actual.unwrap_or(expected), expected, "init: {:X}, start: {}, len: {}", init, start, len);
+                    if cfg!(target_arch = "x86_64") {
+                        assert_eq!(actual.is_some(), len >= 64, "len: {}", len);
+                    } else {
+                        assert!(actual.is_some(), "len: {}", len);
+                    }
+                    if len < data.len() - start {
+                        expected = spec.update(expected, data[start + len]);
+                    }
+                }
+            }
+        }
+        let mut zeros = spec.start();
+        for _ in 0..4096 {
+            zeros = spec.update(zeros, 0);
+        }
+        assert_eq!(checksum_with_state(&spec, spec.start(), &[0; 4096]), Some(zeros));
+        let ones = (0..4096).fold(spec.start(), |value, _| spec.update(value, 0xFF));
+        assert_eq!(checksum_with_state(&spec, spec.start(), &[0xFF; 4096]), Some(ones));
+        let wide = CrcTable::new_with_width(32, 0x04C11DB7u64, 0, true, false, 0);
+        assert_eq!(checksum_with_state(&wide, 0xFFFFFFFF, &data), Some(data.iter().fold(0xFFFFFFFF, |value, &b| wide.update(value, b))));
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn clmul_constants() {
+        use super::clmul::*;
+        // x^n mod P, unreflected.
+        fn xpow_mod(n: u32) -> u32 {
+            (0..n).fold(1u32, |r, _| if r & 0x80000000 != 0 { (r << 1) ^ 0x04C11DB7 } else { r << 1 })
+        }
+        fn key(n: u32) -> i64 {
+            i64::from(xpow_mod(n).reverse_bits()) << 1
+        }
+        assert_eq!((K1, K2), (key(4 * 128 + 32), key(4 * 128 - 32)));
+        assert_eq!((K3, K4), (key(128 + 32), key(128 - 32)));
+        assert_eq!(K5, key(64));
+        assert_eq!(P, i64::from(0x04C11DB7u32.reverse_bits()) << 1 | 1);
+        let (mut remainder, mut quotient) = (1u128 << 64, 0u64);
+        for i in (0..=32).rev() {
+            if remainder & (1 << (i + 32)) != 0 {
+                remainder ^= 0x104C11DB7 << i;
+                quotient |= 1 << i;
+            }
+        }
+        assert_eq!(MU, (quotient.reverse_bits() >> 31) as i64);
+    }
+
+    #[test]
+    fn other_specs() {
+        let autosar = CrcTable::new(0xF4ACFB13u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_eq!(checksum_with_state(&autosar, autosar.start(), &data()), None);
+        let non_reflected = CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32);
+        assert_eq!(checksum_with_state(&non_reflected, non_reflected.start(), b"123456789"), None);
+        let narrow = CrcTable::new_with_width(31, 0x1EDC6F41u32, 0, true, true, 0);
+        assert_eq!(checksum_with_state(&narrow, narrow.start(), b"123456789"), None);
+        let bzip2 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32);
+        assert_eq!(checksum_with_state(&bzip2, bzip2.start(), &data()), None);
+        if cfg!(target_arch = "x86_64") {
+            assert_eq!(checksum_with_state(&crc32_spec(), 0, b"123456789"), None);
+        }
+    }
+}
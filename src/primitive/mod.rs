@@ -1,6 +1,13 @@
-//! Implementations of `CrcSpec` and `CrcHasher` for primitive types `u8`, `u16`, `u32`, `u64`
-//! and `usize`. The width of the polynom used for each type corresponds to the type's bit size,
-//! e. g. CRC32 variants are backed by `u32`.
+//! Implementations of `CrcSpec` and `CrcHasher` for primitive types `u8`, `u16`, `u32`, `u64`,
+//! `u128` and `usize`. By default, the width of the polynom used for each type corresponds to the type's
+//! bit size, e. g. CRC32 variants are backed by `u32`. Algorithms of other widths (say, CRC-24)
+//! can be backed by a wider type using `CrcTable::new_with_width`.
+//!
+//! The exception is `usize`, whose default width is 32 bits on every platform,
+//! so that the computed checksums don't depend on the target's pointer width.
+//!
+//! Algorithms wider than 128 bits, or on targets without efficient `u128`, can use
+//! the byte array backed `Wide` type instead.
 //!
 //! # Examples
 //!
@@ -20,13 +27,34 @@
 //! ```
 
 
+#[macro_use] mod newtype;
 mod table;
 mod spec;
+mod params;
+mod blob;
+mod builder;
 mod hasher;
+mod sliced;
+mod nibble;
+mod bit;
+mod fixed;
+mod rolling;
+mod error;
+mod wide;
+mod parse;
+mod format;
+#[cfg(feature = "digest")] mod digest;
+#[cfg(feature = "serde")] mod serde;
+#[cfg(feature = "num-traits")] mod num;
+#[cfg(feature = "rayon")] mod parallel;
+#[cfg(feature = "hw")] mod hw;
+pub mod reference;
+pub mod poly;
+#[cfg(test)] mod test_util;
 
-use std::ops::{Not, Shl, Shr, BitAnd, BitXor};
-use bit_reverse::ParallelReverse;
-use std::fmt::Debug;
+use core::ops::{Not, Shl, Shr, BitAnd, BitXor, Deref};
+use core::fmt::Debug;
+use core::mem::size_of;
 
 
 /// A trait that extends all the operation traits necessary for
@@ -38,29 +66,110 @@ use std::fmt::Debug;
 ///
 /// This was intended for use in abstracting out concrete unsigned integer types,
 /// but nobody prevents you from rolling your own implementation.
+/// For newtypes around a type that implements `ValueType`, the
+/// [`newtype_value_type!`](../macro.newtype_value_type.html) macro does that for you.
 pub trait ValueType:
     From<u8> +
     Not<Output=Self> +
     Shl<u8, Output=Self> +
     Shr<u8, Output=Self> +
-    ParallelReverse<Self> +
     BitXor<Self, Output=Self> +
     BitAnd<Self, Output=Self> +
     Eq +
     Copy +
     Debug
 {
+    /// The bit size of the type.
+    const BITS: u32 = (size_of::<Self>() * 8) as u32;
+
     /// `Into<u8>` is apparently not implemented by default for narrowing conversions
     /// of primitive integers, so I decided to make that into a special method. This one.
     fn to_u8(self) -> u8;
+
+    /// Reverses the order of all bits in the value.
+    /// For the built-in implementations, this is the inherent `reverse_bits` of the type.
+    fn reverse_bits(self) -> Self;
+
+    /// The CRC width used by [`CrcTable::new`](struct.CrcTable.html#method.new).
+    /// Defaults to the bit size of the type.
+    fn default_width() -> usize {
+        Self::BITS as usize
+    }
+
+    /// Returns a value with the low `width` bits set.
+    /// `width` must be between 1 and `BITS`, inclusive.
+    fn mask(width: usize) -> Self {
+        !Self::from(0) >> ((Self::BITS as usize - width) as u8)
+    }
+
+    /// Shifts the value left by `n` bits, or returns `None` if `n` is not less than `BITS`.
+    fn checked_shl(self, n: usize) -> Option<Self> {
+        if n < Self::BITS as usize { Some(self << (n as u8)) } else { None }
+    }
+
+    /// Shifts the value right by `n` bits, or returns `None` if `n` is not less than `BITS`.
+    fn checked_shr(self, n: usize) -> Option<Self> {
+        if n < Self::BITS as usize { Some(self >> (n as u8)) } else { None }
+    }
+
+    /// Returns the low `width` bits of the value as bytes, most significant byte first.
+    /// The number of bytes is the smallest one that fits `width` bits.
+    fn be_bytes(self, width: usize) -> RegisterBytes {
+        let mut result = RegisterBytes { bytes: [0; MAX_BYTES], len: width.div_ceil(8) };
+        for (i, b) in result.bytes[..result.len].iter_mut().rev().enumerate() {
+            *b = (self >> ((i * 8) as u8)).to_u8();
+        }
+        result
+    }
+
+    /// Returns the low `width` bits of the value as bytes, least significant byte first.
+    /// The number of bytes is the smallest one that fits `width` bits.
+    fn le_bytes(self, width: usize) -> RegisterBytes {
+        let mut result = self.be_bytes(width);
+        result.bytes[..result.len].reverse();
+        result
+    }
+}
+
+/// The largest supported size of a `ValueType`, in bytes. Shift amounts are `u8`,
+/// so larger types couldn't be shifted by their whole width.
+const MAX_BYTES: usize = 32;
+
+/// The bytes of a register value, as returned by
+/// [`ValueType::be_bytes`](trait.ValueType.html#method.be_bytes) and
+/// [`ValueType::le_bytes`](trait.ValueType.html#method.le_bytes).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegisterBytes {
+    bytes: [u8; MAX_BYTES],
+    len: usize,
+}
+
+impl AsRef<[u8]> for RegisterBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Deref for RegisterBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_ref()
+    }
 }
 
 macro_rules! impl_value_type {
     ($t:ty) => {
         impl ValueType for $t {
+            const BITS: u32 = <$t>::BITS;
+
             fn to_u8(self) -> u8 {
                 self as u8
             }
+
+            fn reverse_bits(self) -> Self {
+                <$t>::reverse_bits(self)
+            }
         }
     };
 }
@@ -68,9 +177,118 @@ impl_value_type!(u8);
 impl_value_type!(u16);
 impl_value_type!(u32);
 impl_value_type!(u64);
-impl_value_type!(usize);
+impl_value_type!(u128);
+
+/// The bit size of `usize` depends on the target platform, which would make checksums
+/// computed by `CrcTable::<usize>::new` platform-dependent as well.
+/// To avoid that, the default width for `usize` is fixed at 32 bits,
+/// so `usize` behaves exactly like `u32` unless a different width is requested explicitly.
+impl ValueType for usize {
+    const BITS: u32 = usize::BITS;
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    fn reverse_bits(self) -> Self {
+        usize::reverse_bits(self)
+    }
+
+    fn default_width() -> usize {
+        32
+    }
+}
+
+/// Reverses the order of the low `width` bits of `value`.
+/// The bits above `width` are expected to be zero.
+fn reflect<T: ValueType>(value: T, width: usize) -> T {
+    value.reverse_bits() >> ((T::BITS as usize - width) as u8)
+}
 
 
-use self::table::*;
+#[doc(hidden)]
+pub use core::ops as __ops;
+
+pub use self::table::*;
 pub use self::spec::*;
+pub use self::params::*;
+pub use self::blob::*;
+pub use self::builder::*;
 pub use self::hasher::*;
+pub use self::sliced::*;
+pub use self::nibble::*;
+pub use self::bit::*;
+pub use self::fixed::*;
+pub use self::rolling::*;
+pub use self::error::*;
+pub use self::wide::*;
+#[cfg(feature = "codegen")] pub(crate) use self::format::Hex;
+#[cfg(feature = "num-traits")] pub use self::num::*;
+
+
+#[cfg(test)]
+mod tests {
+    macro_rules! value_type_tests_for {
+        ($t:ty, $module:ident) => {
+            mod $module {
+                use super::super::ValueType;
+                use core::mem::size_of;
+
+                #[test]
+                fn bits() {
+                    assert_eq!(<$t as ValueType>::BITS, <$t>::BITS);
+                }
+
+                #[test]
+                fn mask() {
+                    for width in 1..=(<$t>::BITS as usize) {
+                        let expected = if width == <$t>::BITS as usize { <$t>::MAX } else { (1 << width) - 1 };
+                        assert_eq!(<$t as ValueType>::mask(width), expected, "width {}", width);
+                    }
+                }
+
+                #[test]
+                fn checked_shifts() {
+                    let value: $t = 0xA5;
+                    for n in 0..(<$t>::BITS as usize) {
+                        assert_eq!(ValueType::checked_shl(value, n), <$t>::checked_shl(value, n as u32));
+                        assert_eq!(ValueType::checked_shr(value, n), <$t>::checked_shr(value, n as u32));
+                    }
+                    assert_eq!(ValueType::checked_shl(value, <$t>::BITS as usize), None);
+                    assert_eq!(ValueType::checked_shr(value, 1000), None);
+                }
+
+                #[test]
+                fn bytes() {
+                    let value = <$t>::MAX / 0xFF * 0x35 ^ 0x1234u16 as $t;
+                    assert_eq!(&value.be_bytes(<$t>::BITS as usize)[..], &value.to_be_bytes()[..]);
+                    assert_eq!(&value.le_bytes(<$t>::BITS as usize)[..], &value.to_le_bytes()[..]);
+                    for width in 1..=(<$t>::BITS as usize) {
+                        let len = width.div_ceil(8);
+                        let masked = value & <$t as ValueType>::mask(width);
+                        assert_eq!(masked.be_bytes(width).len(), len);
+                        assert_eq!(&masked.be_bytes(width)[..], &masked.to_be_bytes()[size_of::<$t>() - len..]);
+                        assert_eq!(&masked.le_bytes(width)[..], &masked.to_le_bytes()[..len]);
+                    }
+                }
+            }
+        }
+    }
+
+    value_type_tests_for!(u8, test_u8);
+    value_type_tests_for!(u16, test_u16);
+    value_type_tests_for!(u32, test_u32);
+    value_type_tests_for!(u64, test_u64);
+    value_type_tests_for!(u128, test_u128);
+    value_type_tests_for!(usize, test_usize);
+
+    #[test]
+    fn wide() {
+        use super::{ValueType, Wide};
+        assert_eq!(Wide::<11>::BITS, 88);
+        assert_eq!(Wide::<3>::mask(12), Wide([0, 0x0F, 0xFF]));
+        assert_eq!(Wide([1, 2, 3]).be_bytes(24).as_ref(), [1, 2, 3]);
+        assert_eq!(Wide([1, 2, 3]).le_bytes(16).as_ref(), [3, 2]);
+        assert_eq!(Wide([1, 2, 3]).checked_shl(24), None);
+    }
+}
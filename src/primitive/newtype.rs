@@ -0,0 +1,127 @@
+/// Implements [`ValueType`](primitive/trait.ValueType.html) for a newtype
+/// around a type that implements it, forwarding all the operations to the wrapped value.
+///
+/// The newtype itself has to derive (or otherwise implement)
+/// `Clone`, `Copy`, `PartialEq`, `Eq` and `Debug`.
+///
+/// ```
+/// #[macro_use] extern crate crc_rocksoft;
+/// use crc_rocksoft::primitive::CrcTable;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// struct Crc32Value(u32);
+/// newtype_value_type!(Crc32Value(u32));
+///
+/// # fn main() {
+/// let spec = CrcTable::new(Crc32Value(0x04C11DB7), Crc32Value(!0), true, true, Crc32Value(!0));
+/// assert_eq!(spec.check(), Crc32Value(0xCBF43926));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! newtype_value_type {
+    ($name:ident($inner:ty)) => {
+        impl From<u8> for $name {
+            fn from(value: u8) -> Self {
+                $name(<$inner as From<u8>>::from(value))
+            }
+        }
+
+        impl $crate::primitive::__ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                $name(!self.0)
+            }
+        }
+
+        impl $crate::primitive::__ops::Shl<u8> for $name {
+            type Output = Self;
+
+            fn shl(self, rhs: u8) -> Self {
+                $name(self.0 << rhs)
+            }
+        }
+
+        impl $crate::primitive::__ops::Shr<u8> for $name {
+            type Output = Self;
+
+            fn shr(self, rhs: u8) -> Self {
+                $name(self.0 >> rhs)
+            }
+        }
+
+        impl $crate::primitive::__ops::BitXor for $name {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self {
+                $name(self.0 ^ rhs.0)
+            }
+        }
+
+        impl $crate::primitive::__ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                $name(self.0 & rhs.0)
+            }
+        }
+
+        impl $crate::primitive::ValueType for $name {
+            const BITS: u32 = <$inner as $crate::primitive::ValueType>::BITS;
+
+            fn to_u8(self) -> u8 {
+                $crate::primitive::ValueType::to_u8(self.0)
+            }
+
+            fn reverse_bits(self) -> Self {
+                $name($crate::primitive::ValueType::reverse_bits(self.0))
+            }
+
+            fn default_width() -> usize {
+                <$inner as $crate::primitive::ValueType>::default_width()
+            }
+        }
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::CrcHasher;
+    use primitive::{ValueType, CrcTable, CrcTableHasher, CrcSlice8Table, CrcSlicedTableHasher};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct Crc32Value(u32);
+    newtype_value_type!(Crc32Value(u32));
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct Crc32CValue(u32);
+    newtype_value_type!(Crc32CValue(u32));
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct PlatformValue(usize);
+    newtype_value_type!(PlatformValue(usize));
+
+    #[test]
+    fn crc32() {
+        let spec = CrcTable::new(Crc32Value(0x04C11DB7), Crc32Value(!0), true, true, Crc32Value(!0));
+        let mut hasher = CrcTableHasher::from(&spec);
+        hasher.update_from_slice(b"123456789");
+        assert_eq!(hasher.finish(), Crc32Value(0xCBF43926));
+        assert_eq!(spec.check(), Crc32Value(0xCBF43926));
+    }
+
+    #[test]
+    fn crc32c_sliced() {
+        let spec = CrcSlice8Table::from(CrcTable::new(Crc32CValue(0x1EDC6F41), Crc32CValue(!0), true, true, Crc32CValue(!0)));
+        let mut hasher = CrcSlicedTableHasher::from(&spec);
+        hasher.update_from_slice(b"123456789");
+        assert_eq!(hasher.finish(), Crc32CValue(0xE3069283));
+    }
+
+    #[test]
+    fn forwards_default_width() {
+        assert_eq!(Crc32Value::default_width(), 32);
+        assert_eq!(PlatformValue::default_width(), 32);
+    }
+}
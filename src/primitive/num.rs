@@ -0,0 +1,158 @@
+//! An adapter for integer types from the `num-traits` ecosystem.
+//! Only available with the `num-traits` feature.
+
+use super::ValueType;
+use core::fmt::Debug;
+use core::ops::{Not, Shl, Shr, BitAnd, BitXor};
+use num_traits::{PrimInt, Unsigned};
+
+
+/// A `ValueType` for any unsigned `num-traits` integer.
+///
+/// `ValueType` can't be implemented for all such types directly: a blanket
+/// `impl<T: PrimInt + Unsigned> ValueType for T` would overlap with the built-in implementations
+/// for primitive integers, and with those of `Wide` and of any newtype, since the compiler
+/// has to assume that any type may implement `PrimInt` in the future. So the value is wrapped
+/// instead. Bit reversal uses `PrimInt::reverse_bits`.
+///
+/// ```
+/// use crc_rocksoft::primitive::{CrcTable, NumValue};
+///
+/// let spec = CrcTable::new(NumValue(0x04C11DB7u32), NumValue(!0), true, true, NumValue(!0));
+/// assert_eq!(spec.check(), NumValue(0xCBF43926));
+/// ```
+///
+/// The wrapper also lets code that is generic over `num-traits` integers compute checksums
+/// without any bounds of this crate:
+///
+/// ```
+/// extern crate num_traits;
+/// # extern crate crc_rocksoft;
+/// use std::fmt::Debug;
+/// use num_traits::{PrimInt, Unsigned};
+/// use crc_rocksoft::primitive::{CrcTable, NumValue};
+///
+/// fn check<T: PrimInt + Unsigned + Debug>(width: usize, poly: T) -> T {
+///     CrcTable::new_with_width(width, NumValue(poly), NumValue(T::zero()), false, false, NumValue(T::zero())).check().0
+/// }
+///
+/// # fn main() {
+/// assert_eq!(check(16, 0x1021u16), 0x31C3);
+/// assert_eq!(check(24, 0x864CFBu64), 0xCDE703);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NumValue<T>(pub T);
+
+impl<T: PrimInt + Unsigned> From<u8> for NumValue<T> {
+    fn from(value: u8) -> Self {
+        NumValue(T::from(value).expect("an unsigned integer type narrower than u8"))
+    }
+}
+
+impl<T: PrimInt + Unsigned> Not for NumValue<T> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        NumValue(!self.0)
+    }
+}
+
+impl<T: PrimInt + Unsigned> Shl<u8> for NumValue<T> {
+    type Output = Self;
+
+    fn shl(self, rhs: u8) -> Self {
+        NumValue(self.0 << rhs as usize)
+    }
+}
+
+impl<T: PrimInt + Unsigned> Shr<u8> for NumValue<T> {
+    type Output = Self;
+
+    fn shr(self, rhs: u8) -> Self {
+        NumValue(self.0 >> rhs as usize)
+    }
+}
+
+impl<T: PrimInt + Unsigned> BitXor for NumValue<T> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        NumValue(self.0 ^ rhs.0)
+    }
+}
+
+impl<T: PrimInt + Unsigned> BitAnd for NumValue<T> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        NumValue(self.0 & rhs.0)
+    }
+}
+
+impl<T: PrimInt + Unsigned + Debug> ValueType for NumValue<T> {
+    fn to_u8(self) -> u8 {
+        (self.0 & T::from(0xFFu8).unwrap()).to_u8().unwrap()
+    }
+
+    fn reverse_bits(self) -> Self {
+        NumValue(self.0.reverse_bits())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::{CrcSpec, CrcHasher};
+    use super::NumValue;
+    use super::super::{ValueType, CrcTable, CrcTableHasher};
+
+    macro_rules! num_tests_for {
+        ($t:ty, $module:ident, $poly:expr, $width:expr) => {
+            mod $module {
+                use super::*;
+
+                #[test]
+                fn same_as_builtin() {
+                    let data: std::vec::Vec<u8> = (0..=255).collect();
+                    for &(refin, refout) in &[(false, false), (true, true), (true, false), (false, true)] {
+                        let builtin = CrcTable::new_with_width($width, $poly as $t, 3 as $t, refin, refout, 5 as $t);
+                        let num = CrcTable::new_with_width($width, NumValue($poly as $t), NumValue(3), refin, refout, NumValue(5));
+                        for (a, b) in builtin.table().iter().zip(num.table().iter()) {
+                            assert_eq!(NumValue(*a), *b);
+                        }
+                        let mut h1 = CrcTableHasher::from(&builtin);
+                        let mut h2 = CrcTableHasher::from(&num);
+                        h1.update_from_slice(&data);
+                        h2.update_from_slice(&data);
+                        assert_eq!(NumValue(h1.finish()), h2.finish());
+                        assert_eq!(NumValue(builtin.residue()), num.residue());
+                    }
+                }
+
+                #[test]
+                fn value_type() {
+                    let value: $t = ($poly as $t) ^ 0xA5;
+                    assert_eq!(NumValue(value).to_u8(), ValueType::to_u8(value));
+                    assert_eq!(NumValue(value).reverse_bits(), NumValue(ValueType::reverse_bits(value)));
+                    assert_eq!(NumValue::<$t>::BITS, <$t as ValueType>::BITS);
+                    assert_eq!(NumValue::<$t>::default_width(), <$t>::BITS as usize);
+                }
+            }
+        }
+    }
+
+    num_tests_for!(u8, test_u8, 0x07, 8);
+    num_tests_for!(u16, test_u16, 0x8005, 16);
+    num_tests_for!(u32, test_u32, 0x04C11DB7, 32);
+    num_tests_for!(u32, test_u32_narrow, 0x864CFB, 24);
+    num_tests_for!(u64, test_u64, 0x42F0E1EBA9EA3693u64, 64);
+    num_tests_for!(u128, test_u128, 0x0308C0111011401440411u128, 82);
+
+    #[test]
+    fn crc32_check() {
+        let spec = CrcTable::new(NumValue(0x04C11DB7u32), NumValue(!0), true, true, NumValue(!0));
+        assert_eq!(spec.check(), NumValue(0xCBF43926));
+        assert_eq!(spec.width(), 32);
+    }
+}
@@ -0,0 +1,80 @@
+//! Parallel checksum computation. Only available with the `rayon` feature.
+
+use ::CrcHasher;
+use super::{ValueType, CrcTable, CrcTableHasher};
+use rayon::prelude::*;
+
+
+/// Slices shorter than this are not worth splitting.
+const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+impl<T: ValueType + Send + Sync> CrcTable<T> {
+
+    /// Computes the checksum of `data`, splitting it into chunks whose checksums are
+    /// computed in parallel on the `rayon` thread pool and then joined
+    /// with [`combine`](#method.combine).
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// assert_eq!(crc32.par_checksum(b"123456789"), 0xCBF43926);
+    /// ```
+    pub fn par_checksum(&self, data: &[u8]) -> T {
+        let chunk_len = data.len().div_ceil(rayon::current_num_threads()).max(MIN_CHUNK_LEN);
+        self.par_checksum_with_chunk_len(data, chunk_len)
+    }
+
+    fn par_checksum_with_chunk_len(&self, data: &[u8], chunk_len: usize) -> T {
+        let empty = self.finish(self.start());
+        data.par_chunks(chunk_len)
+            .map(|chunk| {
+                let mut hasher = CrcTableHasher::from(self);
+                hasher.update_from_slice(chunk);
+                (hasher.finish(), chunk.len())
+            })
+            .reduce(
+                || (empty, 0),
+                |(crc_a, len_a), (crc_b, len_b)| (self.combine(crc_a, crc_b, len_b), len_a + len_b)
+            )
+            .0
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use primitive::test_util::Random;
+    use ::CrcHasher;
+    use super::super::{CrcTable, CrcTableHasher};
+
+    fn sequential<T: ::primitive::ValueType>(spec: &CrcTable<T>, data: &[u8]) -> T {
+        let mut hasher = CrcTableHasher::from(spec);
+        hasher.update_from_slice(data);
+        hasher.finish()
+    }
+
+    #[test]
+    fn matches_sequential() {
+        let data = Random(0x2545F4914F6CDD1D).data(100_000);
+        let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+        let crc64 = CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64);
+        for &chunks in &[1, 2, 3, 7, 16, 1000] {
+            let chunk_len = data.len().div_ceil(chunks);
+            assert_eq!(crc32.par_checksum_with_chunk_len(&data, chunk_len), sequential(&crc32, &data), "{} chunks", chunks);
+            assert_eq!(crc24.par_checksum_with_chunk_len(&data, chunk_len), sequential(&crc24, &data), "{} chunks", chunks);
+            assert_eq!(crc64.par_checksum_with_chunk_len(&data, chunk_len), sequential(&crc64, &data), "{} chunks", chunks);
+        }
+        assert_eq!(crc32.par_checksum(&data), sequential(&crc32, &data));
+    }
+
+    #[test]
+    fn empty() {
+        let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_eq!(crc32.par_checksum(&[]), 0);
+        let crc16 = CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16);
+        assert_eq!(crc16.par_checksum(&[]), 0xFFFF);
+    }
+}
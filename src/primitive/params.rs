@@ -0,0 +1,153 @@
+use ::CrcSpec;
+use super::{ValueType, CrcTable, CrcError, Table};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+
+
+/// The parameters of an algorithm without the lookup table, e. g. for storing them
+/// in a configuration file (with the `serde` feature), or for keeping many candidate
+/// algorithms around cheaply. Convert them to a `CrcTable` with `TryFrom`,
+/// which validates them, when checksums are to be computed.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use crc_rocksoft::primitive::{CrcParams, CrcTable};
+///
+/// let params = CrcParams { width: 16, poly: 0x1021u16, init: 0xFFFF, refin: false, refout: false, xorout: 0 };
+/// let spec = CrcTable::try_from(params).unwrap();
+/// assert_eq!(spec.check(), 0x29B1);
+/// assert_eq!(spec.params(), params);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CrcParams<T> {
+    /// See [`CrcSpec::width`](../trait.CrcSpec.html#tymethod.width).
+    pub width: usize,
+    /// See [`CrcSpec::poly`](../trait.CrcSpec.html#tymethod.poly).
+    pub poly: T,
+    /// See [`CrcSpec::init`](../trait.CrcSpec.html#tymethod.init).
+    pub init: T,
+    /// See [`CrcSpec::refin`](../trait.CrcSpec.html#tymethod.refin).
+    pub refin: bool,
+    /// See [`CrcSpec::refout`](../trait.CrcSpec.html#tymethod.refout).
+    pub refout: bool,
+    /// See [`CrcSpec::xorout`](../trait.CrcSpec.html#tymethod.xorout).
+    pub xorout: T,
+}
+
+impl<T: ValueType> CrcSpec<T> for CrcParams<T> {
+    fn width(&self) -> usize { self.width }
+    fn poly(&self) -> T { self.poly }
+    fn init(&self) -> T { self.init }
+    fn refin(&self) -> bool { self.refin }
+    fn refout(&self) -> bool { self.refout }
+    fn xorout(&self) -> T { self.xorout }
+}
+
+impl<T: ValueType> CrcParams<T> {
+    /// Copies the parameters of any spec.
+    pub fn from_spec(spec: &impl CrcSpec<T>) -> Self {
+        CrcParams {
+            width: spec.width(),
+            poly: spec.poly(),
+            init: spec.init(),
+            refin: spec.refin(),
+            refout: spec.refout(),
+            xorout: spec.xorout(),
+        }
+    }
+}
+
+impl<T: ValueType> CrcTable<T> {
+    /// Builds the lookup table for the parameters of any spec.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_from_spec`](#method.try_from_spec).
+    pub fn from_spec(spec: &impl CrcSpec<T>) -> Self {
+        CrcTable::try_from_spec(spec).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Builds the lookup table for the parameters of any spec.
+    ///
+    /// Returns an error if the parameters are invalid; see
+    /// [`try_new_with_width`](#method.try_new_with_width).
+    pub fn try_from_spec(spec: &impl CrcSpec<T>) -> Result<Self, CrcError<T>> {
+        CrcTable::try_from(CrcParams::from_spec(spec))
+    }
+}
+
+impl<T: ValueType, B: Borrow<Table<T>>> CrcTable<T, B> {
+    /// Returns the parameters of the algorithm.
+    pub fn params(&self) -> CrcParams<T> {
+        CrcParams::from_spec(self)
+    }
+}
+
+/// Fails if the parameters are invalid; see
+/// [`CrcTable::try_new_with_width`](struct.CrcTable.html#method.try_new_with_width).
+impl<T: ValueType> TryFrom<CrcParams<T>> for CrcTable<T> {
+    type Error = CrcError<T>;
+
+    fn try_from(p: CrcParams<T>) -> Result<Self, Self::Error> {
+        CrcTable::try_new_with_width(p.width, p.poly, p.init, p.refin, p.refout, p.xorout)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CrcParams, CrcTable, CrcError};
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+        let params = spec.params();
+        assert_eq!(params, CrcParams { width: 24, poly: 0x864CFB, init: 0xB704CE, refin: false, refout: false, xorout: 0 });
+        assert!(CrcTable::try_from(params).unwrap() == spec);
+    }
+
+    #[test]
+    fn from_spec() {
+        use std::collections::HashSet;
+        let candidates: HashSet<CrcParams<u16>> = [(false, false), (true, true), (true, false), (false, true)].iter()
+            .map(|&(refin, refout)| CrcParams { width: 16, poly: 0x1021, init: 0xFFFF, refin, refout, xorout: 0 })
+            .collect();
+        assert_eq!(candidates.len(), 4);
+        for params in &candidates {
+            let direct = CrcTable::new(0x1021u16, 0xFFFF, params.refin, params.refout, 0);
+            let built = CrcTable::from_spec(params);
+            assert!(built == direct);
+            assert_eq!(&built.table()[..], &direct.table()[..]);
+            assert_eq!(built.check(), direct.check());
+        }
+    }
+
+    #[test]
+    fn from_other_spec() {
+        let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+        let wide = CrcTable::from_spec(&CrcParams::<u64> {
+            width: 24, poly: 0x864CFB, init: 0xB704CE, refin: false, refout: false, xorout: 0,
+        });
+        assert_eq!(wide.check(), u64::from(crc24.check()));
+        assert_eq!(CrcParams::from_spec(&crc24), crc24.params());
+    }
+
+    #[test]
+    fn try_from_spec_invalid() {
+        let params = CrcParams { width: 16, poly: 0u16, init: 0, refin: false, refout: false, xorout: 0 };
+        assert_eq!(CrcTable::try_from_spec(&params).err(), Some(CrcError::ZeroPoly));
+    }
+
+    #[test]
+    #[should_panic(expected = "the poly must not be zero")]
+    fn from_spec_panics() {
+        CrcTable::from_spec(&CrcParams { width: 16, poly: 0u16, init: 0, refin: false, refout: false, xorout: 0 });
+    }
+
+    #[test]
+    fn invalid() {
+        let params = CrcParams { width: 12, poly: 0x80Fu16, init: 0, refin: false, refout: true, xorout: 0x1000 };
+        assert_eq!(CrcTable::try_from(params).err(), Some(CrcError::ValueExceedsWidth { field: "xorout", value: 0x1000 }));
+    }
+}
@@ -0,0 +1,269 @@
+use super::{ValueType, CrcTable, CrcError};
+use core::str::FromStr;
+
+
+/// Parses an algorithm in the format of the
+/// [CRC RevEng catalogue](http://reveng.sourceforge.net/crc-catalogue/all.htm),
+/// e. g. `width=32 poly=0x04c11db7 init=0xffffffff refin=true refout=true xorout=0xffffffff`.
+///
+/// The parameters are separated by whitespace and may come in any order.
+/// `width` is decimal, the values are hexadecimal with the `0x` prefix, as in the catalogue,
+/// or decimal without it (so `poly=8005` is `0x1F45`), and `refin` and `refout` are `true` or `false`.
+/// The values must fit in `width` bits. Each parameter may be listed only once.
+/// If the `check` or `residue` parameters are present, they are verified against
+/// the parsed algorithm. Other parameters (such as `name`) are ignored.
+///
+/// ```
+/// use crc_rocksoft::primitive::CrcTable;
+///
+/// let spec: CrcTable<u32> = "width=32 poly=0x04c11db7 init=0xffffffff refin=true refout=true xorout=0xffffffff"
+///     .parse().unwrap();
+/// assert_eq!(spec.check(), 0xCBF43926);
+/// ```
+impl<T: ValueType> FromStr for CrcTable<T> {
+    type Err = CrcError<T>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut width = None;
+        let mut poly = None;
+        let mut init = None;
+        let mut refin = None;
+        let mut refout = None;
+        let mut xorout = None;
+        let mut check = None;
+        let mut residue = None;
+
+        for param in s.split_whitespace() {
+            let (key, value) = match param.find('=') {
+                Some(ix) => (&param[..ix], &param[ix + 1..]),
+                None => continue,
+            };
+            match key {
+                "width" => set(&mut width, "width", value.parse().ok())?,
+                "poly" => set(&mut poly, "poly", parse_number(value))?,
+                "init" => set(&mut init, "init", parse_number(value))?,
+                "refin" => set(&mut refin, "refin", parse_bool(value))?,
+                "refout" => set(&mut refout, "refout", parse_bool(value))?,
+                "xorout" => set(&mut xorout, "xorout", parse_number(value))?,
+                "check" => set(&mut check, "check", parse_number(value))?,
+                "residue" => set(&mut residue, "residue", parse_number(value))?,
+                _ => {}
+            }
+        }
+
+        let spec = CrcTable::try_new_with_width(
+            width.ok_or(CrcError::MissingField("width"))?,
+            poly.ok_or(CrcError::MissingField("poly"))?,
+            init.ok_or(CrcError::MissingField("init"))?,
+            refin.ok_or(CrcError::MissingField("refin"))?,
+            refout.ok_or(CrcError::MissingField("refout"))?,
+            xorout.ok_or(CrcError::MissingField("xorout"))?,
+        )?;
+        if let Some(expected) = check {
+            verify("check", expected, spec.check())?;
+        }
+        if let Some(expected) = residue {
+            verify("residue", expected, spec.residue())?;
+        }
+        Ok(spec)
+    }
+}
+
+fn set<V, T>(slot: &mut Option<V>, field: &'static str, value: Option<V>) -> Result<(), CrcError<T>> {
+    if slot.is_some() {
+        return Err(CrcError::DuplicateField(field));
+    }
+    *slot = Some(value.ok_or(CrcError::InvalidField(field))?);
+    Ok(())
+}
+
+fn verify<T: ValueType>(field: &'static str, expected: T, actual: T) -> Result<(), CrcError<T>> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(CrcError::Mismatch { field, expected, actual })
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses a hexadecimal number with the `0x` prefix, or a decimal number without it,
+/// returning `None` if it doesn't fit in `T`. Decimal numbers are limited to 128 bits.
+fn parse_number<T: ValueType>(s: &str) -> Option<T> {
+    if s.starts_with("0x") || s.starts_with("0X") {
+        return parse_hex(s);
+    }
+    // `u128::from_str` would accept a leading `+`.
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    from_u128(s.parse().ok()?)
+}
+
+/// Converts `v` to `T`, or returns `None` if it doesn't fit.
+pub(super) fn from_u128<T: ValueType>(v: u128) -> Option<T> {
+    let bits = 128 - v.leading_zeros() as usize;
+    if bits > T::BITS as usize {
+        return None;
+    }
+    Some((0..bits.div_ceil(8)).rev().fold(T::from(0), |value, i| {
+        value.checked_shl(8).unwrap_or(T::from(0)) ^ T::from((v >> (i * 8)) as u8)
+    }))
+}
+
+/// Parses a hexadecimal number, returning `None` if it doesn't fit in `T`.
+pub(super) fn parse_hex<T: ValueType>(s: &str) -> Option<T> {
+    let digits = if s.starts_with("0x") || s.starts_with("0X") { &s[2..] } else { s };
+    if digits.is_empty() {
+        return None;
+    }
+    let zero = T::from(0);
+    let mut value = zero;
+    let mut significant_bits = 0;
+    for c in digits.chars() {
+        let digit = c.to_digit(16)? as u8;
+        if value != zero || digit != 0 {
+            significant_bits += 4;
+            if significant_bits > T::BITS as usize {
+                return None;
+            }
+        }
+        value = (value << 4) ^ T::from(digit);
+    }
+    Some(value)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CrcTable, CrcError};
+    use ::CrcSpec;
+    use std::string::ToString;
+
+    const CRC32: &str = "width=32 poly=0x04c11db7 init=0xffffffff refin=true refout=true xorout=0xffffffff";
+
+    #[test]
+    fn crc32() {
+        let spec: CrcTable<u32> = CRC32.parse().unwrap();
+        assert_eq!(spec.width(), 32);
+        assert_eq!(spec.poly(), 0x04C11DB7);
+        assert_eq!(spec.init(), 0xFFFFFFFF);
+        assert!(spec.refin());
+        assert!(spec.refout());
+        assert_eq!(spec.xorout(), 0xFFFFFFFF);
+        assert_eq!(spec.check(), 0xCBF43926);
+    }
+
+    #[test]
+    fn catalogue_line() {
+        let line = "width=32  poly=0x04c11db7  init=0xffffffff  refin=true  refout=true  xorout=0xffffffff  \
+                    check=0xcbf43926  residue=0xdebb20e3  name=\"CRC-32/ISO-HDLC\"";
+        assert!(line.parse::<CrcTable<u32>>().is_ok());
+    }
+
+    #[test]
+    fn narrow_width() {
+        let spec: CrcTable<u32> = "width=24 poly=0x864cfb init=0xb704ce refin=false refout=false xorout=0x000000 check=0x21cf02"
+            .parse().unwrap();
+        assert_eq!(spec.width(), 24);
+        assert_eq!(spec.check(), 0x21CF02);
+    }
+
+    #[test]
+    fn any_order() {
+        let spec: CrcTable<u16> = "xorout=0 refout=true poly=0x8005 width=16 refin=true init=0xFFFF".parse().unwrap();
+        assert_eq!(spec.check(), 0x4B37);
+    }
+
+    #[test]
+    fn decimal_values() {
+        let spec: CrcTable<u32> = "width=32 poly=79764919 init=4294967295 refin=true refout=true xorout=0xffffffff check=3421780262"
+            .parse().unwrap();
+        assert_eq!(spec.poly(), 0x04C11DB7);
+        assert_eq!(spec.check(), 0xCBF43926);
+        let parse = |s: &str| s.parse::<CrcTable<u16>>().err();
+        assert_eq!(parse("width=16 poly=8005 init=0 refin=false refout=false xorout=0"), None);
+        assert_eq!(parse("width=16 poly=65536 init=0 refin=false refout=false xorout=0"), Some(CrcError::InvalidField("poly")));
+        assert_eq!(parse("width=16 poly=+8005 init=0 refin=false refout=false xorout=0"), Some(CrcError::InvalidField("poly")));
+        // Hexadecimal digits need the prefix.
+        assert_eq!(parse("width=16 poly=1021 init=ffff refin=false refout=false xorout=0"), Some(CrcError::InvalidField("init")));
+        let wide: CrcTable<u128> = "width=82 poly=0x0308c0111011401440411 init=0 refin=true refout=true xorout=0".parse().unwrap();
+        assert_eq!(wide.check(), 0x09EA83F625023801FD612);
+    }
+
+    #[test]
+    fn missing_field() {
+        let s = "width=32 poly=0x04c11db7 init=0xffffffff refin=true xorout=0xffffffff";
+        assert_eq!(s.parse::<CrcTable<u32>>().err(), Some(CrcError::MissingField("refout")));
+    }
+
+    #[test]
+    fn invalid_field() {
+        let parse = |s: &str| s.parse::<CrcTable<u32>>().err();
+        assert_eq!(parse(&CRC32.replace("refin=true", "refin=yes")), Some(CrcError::InvalidField("refin")));
+        assert_eq!(parse(&CRC32.replace("poly=0x04c11db7", "poly=0x04c11dbg")), Some(CrcError::InvalidField("poly")));
+        assert_eq!(parse(&CRC32.replace("init=0xffffffff", "init=0x")), Some(CrcError::InvalidField("init")));
+        assert_eq!(parse(&CRC32.replace("width=32", "width=thirty-two")), Some(CrcError::InvalidField("width")));
+        assert_eq!(parse(&CRC32.replace("refout=true", "refout=1")), Some(CrcError::InvalidField("refout")));
+        assert_eq!(parse(&CRC32.replace("refout=true", "refout=TRUE")), Some(CrcError::InvalidField("refout")));
+    }
+
+    #[test]
+    fn duplicate_field() {
+        let parse = |s: &str| s.parse::<CrcTable<u32>>().err();
+        assert_eq!(parse(&format!("{} poly=0x04c11db7", CRC32)), Some(CrcError::DuplicateField("poly")));
+        assert_eq!(parse(&format!("width=32 {}", CRC32)), Some(CrcError::DuplicateField("width")));
+        assert_eq!(parse(&format!("{} check=0xcbf43926 check=0xcbf43926", CRC32)), Some(CrcError::DuplicateField("check")));
+        assert_eq!(CrcError::<u32>::DuplicateField("poly").to_string(), "duplicate parameter: poly");
+    }
+
+    #[test]
+    fn value_exceeds_declared_width() {
+        let parse = |s: &str| s.parse::<CrcTable<u16>>().err();
+        assert_eq!(
+            parse("width=12 poly=0x180f init=0 refin=false refout=true xorout=0"),
+            Some(CrcError::ValueExceedsWidth { field: "poly", value: 0x180F })
+        );
+        assert_eq!(
+            parse("width=12 poly=0x80f init=0 refin=false refout=true xorout=0x1000"),
+            Some(CrcError::ValueExceedsWidth { field: "xorout", value: 0x1000 })
+        );
+        assert_eq!(parse("width=12 poly=0x80f init=0 refin=false refout=true xorout=0 check=0xdaf"), None);
+    }
+
+    #[test]
+    fn value_too_wide_for_type() {
+        let parse = |s: &str| s.parse::<CrcTable<u32>>().err();
+        assert_eq!(parse(&CRC32.replace("xorout=0xffffffff", "xorout=0x1ffffffff")), Some(CrcError::InvalidField("xorout")));
+        assert!(parse(&CRC32.replace("xorout=0xffffffff", "xorout=0x00000000ffffffff")).is_none());
+        assert_eq!(parse(&CRC32.replace("width=32", "width=33")), Some(CrcError::UnsupportedWidth(33)));
+        assert_eq!(
+            parse(&CRC32.replace("width=32", "width=31")),
+            Some(CrcError::ValueExceedsWidth { field: "init", value: 0xFFFFFFFF })
+        );
+    }
+
+    #[test]
+    fn check_mismatch() {
+        let s = format!("{} check=0xcbf43927", CRC32);
+        assert_eq!(
+            s.parse::<CrcTable<u32>>().err(),
+            Some(CrcError::Mismatch { field: "check", expected: 0xCBF43927, actual: 0xCBF43926 })
+        );
+    }
+
+    #[test]
+    fn residue_mismatch() {
+        let s = format!("{} residue=0", CRC32);
+        assert_eq!(
+            s.parse::<CrcTable<u32>>().err(),
+            Some(CrcError::Mismatch { field: "residue", expected: 0, actual: 0xDEBB20E3 })
+        );
+    }
+}
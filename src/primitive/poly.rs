@@ -0,0 +1,104 @@
+//! Conversions between notations of generator polynomials.
+//!
+//! `CrcTable` takes polynomials in the Rocksoft notation, where the implicit `x^width` term
+//! is omitted and the `+1` term is bit 0. Philip Koopman's tables use a different notation:
+//! the `x^width` term is included as the top bit, and the `+1` term is omitted,
+//! so CRC-32's `0x04C11DB7` is written as `0x82608EDB`.
+//!
+//! ```
+//! use crc_rocksoft::primitive::poly::{from_koopman, to_koopman};
+//!
+//! assert_eq!(from_koopman(0x82608EDBu32, 32), 0x04C11DB7);
+//! assert_eq!(to_koopman(0x8005u16, 16), 0xC002);
+//! ```
+//!
+//! Descriptions of reflected algorithms often use the reversed notation instead,
+//! where the bits of the Rocksoft polynomial are in reverse order, e. g. `0xEDB88320` for CRC-32.
+//! Not to be confused with the reciprocal polynomial, which is a different polynomial altogether.
+
+use super::{ValueType, reflect};
+
+
+/// Converts a polynomial of the given width from Koopman notation to Rocksoft notation.
+///
+/// Polynomials in Koopman notation always have the `+1` term, which is restored here.
+pub fn from_koopman<T: ValueType>(koopman: T, width: usize) -> T {
+    ((koopman << 1) ^ T::from(1)) & T::mask(width)
+}
+
+/// Converts a polynomial of the given width from Rocksoft notation to Koopman notation.
+///
+/// The `+1` term of `poly` is dropped, since Koopman notation implies it.
+pub fn to_koopman<T: ValueType>(poly: T, width: usize) -> T {
+    (poly >> 1) ^ (T::from(1) << ((width - 1) as u8))
+}
+
+/// Converts a polynomial of the given width between the Rocksoft notation
+/// and the reversed notation. The conversion is its own inverse.
+pub fn reversed<T: ValueType>(poly: T, width: usize) -> T {
+    reflect(poly, width)
+}
+
+/// Returns the reciprocal of a polynomial of the given width in Rocksoft notation,
+/// i. e. the polynomial with the order of its `width + 1` coefficients reversed.
+///
+/// The polynomial must have the `+1` term, otherwise the result has a lower degree than `width`.
+pub fn reciprocal<T: ValueType>(poly: T, width: usize) -> T {
+    ((reflect(poly, width) << 1) ^ T::from(1)) & T::mask(width)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{from_koopman, to_koopman, reversed, reciprocal};
+
+    #[test]
+    fn known_pairs() {
+        assert_eq!(from_koopman(0x82608EDBu32, 32), 0x04C11DB7);
+        assert_eq!(to_koopman(0x04C11DB7u32, 32), 0x82608EDB);
+        assert_eq!(from_koopman(0xC002u16, 16), 0x8005);
+        assert_eq!(to_koopman(0x8005u16, 16), 0xC002);
+        assert_eq!(from_koopman(0x83u8, 8), 0x07);
+        assert_eq!(from_koopman(0x12u8, 5), 0x05);
+    }
+
+    #[test]
+    fn round_trip_all_widths() {
+        for width in 1..=16 {
+            let top = 1u32 << (width - 1);
+            for poly in (1..(top << 1)).step_by(2) {
+                assert_eq!(from_koopman(to_koopman(poly, width), width), poly, "width {}, poly {:#x}", width, poly);
+            }
+            for koopman in top..(top << 1) {
+                assert_eq!(to_koopman(from_koopman(koopman, width), width), koopman, "width {}, koopman {:#x}", width, koopman);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_u128() {
+        let poly = 0x0308C0111011401440411u128;
+        assert_eq!(to_koopman(poly, 82), 0x0308C0111011401440411u128 >> 1 | 1 << 81);
+        assert_eq!(from_koopman(to_koopman(poly, 82), 82), poly);
+    }
+
+    #[test]
+    fn reversed_pairs() {
+        assert_eq!(reversed(0x04C11DB7u32, 32), 0xEDB88320);
+        assert_eq!(reversed(0xEDB88320u32, 32), 0x04C11DB7);
+        assert_eq!(reversed(0x8005u16, 16), 0xA001);
+        assert_eq!(reversed(0x05u8, 5), 0x14);
+    }
+
+    #[test]
+    fn reciprocal_pairs() {
+        assert_eq!(reciprocal(0x04C11DB7u32, 32), 0xDB710641);
+        assert_eq!(reciprocal(0x8005u16, 16), 0x4003);
+        assert_eq!(reciprocal(0x1021u16, 16), 0x0811);
+        for width in 1..=16 {
+            for poly in (1..(1u32 << width)).step_by(2) {
+                assert_eq!(reciprocal(reciprocal(poly, width), width), poly, "width {}, poly {:#x}", width, poly);
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! A bit-at-a-time implementation of the Rocksoft™ Model.
+//!
+//! This implementation doesn't use a lookup table and processes one message bit per iteration,
+//! in the same way as the simple shift register algorithm in the paper by Ross Williams.
+//! It is slow, but straightforward enough to serve as an oracle when validating
+//! `CrcTable` or any other optimized implementation.
+//!
+//! Unlike `CrcTable`, which keeps the register of a reflected algorithm reflected,
+//! this implementation always operates on the unreflected register, so the intermediate
+//! values can be compared directly with those produced by a hardware shift register.
+//!
+//! # Examples
+//!
+//! ```
+//! use crc_rocksoft::primitive::*;
+//! use crc_rocksoft::primitive::reference::reference_crc;
+//!
+//! let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+//! assert_eq!(reference_crc(&spec, b"123456789"), 0xCBF43926);
+//! ```
+
+use ::CrcSpec;
+use super::{ValueType, reflect};
+
+
+/// Updates an unreflected CRC register with one bit of user data.
+pub fn reference_update_bit<T: ValueType>(spec: &impl CrcSpec<T>, register: T, bit: bool) -> T {
+    let width = spec.width();
+    let top_bit_mask = T::from(1) << ((width - 1) as u8);
+    let feedback = ((register & top_bit_mask) != T::from(0)) != bit;
+    let register = (register << 1) & T::mask(width);
+    if feedback { register ^ spec.poly() } else { register }
+}
+
+/// Updates an unreflected CRC register with one byte of user data, one bit at a time.
+/// The bits of the byte are processed starting from the most significant one,
+/// or from the least significant one if the spec's `refin` is true.
+pub fn reference_update<T: ValueType>(spec: &impl CrcSpec<T>, register: T, byte: u8) -> T {
+    let byte = if spec.refin() { byte.reverse_bits() } else { byte };
+    (0..8).rev().fold(register, |register, i| reference_update_bit(spec, register, (byte >> i) & 1 == 1))
+}
+
+/// Computes the checksum of `data` according to `spec`, processing one bit at a time.
+pub fn reference_crc<T: ValueType>(spec: &impl CrcSpec<T>, data: &[u8]) -> T {
+    let mut register = spec.init();
+    for &byte in data {
+        register = reference_update(spec, register, byte);
+    }
+    (if spec.refout() { reflect(register, spec.width()) } else { register }) ^ spec.xorout()
+}
+
+#[cfg(test)]
+mod tests {
+    use ::CrcHasher;
+    use super::super::{ValueType, CrcTable, CrcTableHasher};
+    use super::reference_crc;
+
+    fn assert_table_matches_reference<T: ValueType>(spec: CrcTable<T>, data: &[u8]) {
+        let expected = reference_crc(&spec, data);
+        let mut h = CrcTableHasher::from(&spec);
+        h.update_from_slice(data);
+        assert_eq!(h.finish(), expected, "data: {:?}", data);
+    }
+
+    #[test]
+    fn all_reflection_combinations() {
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, refin, refout, 0x5A5A5Au32);
+            assert_table_matches_reference(spec, b"123456789");
+        }
+    }
+
+    macro_rules! property_tests_for {
+        ($t:ty, $module:ident) => {
+            mod $module {
+                use super::super::super::CrcTable;
+                use primitive::test_util::Random;
+                use super::{assert_table_matches_reference};
+
+                #[test]
+                fn table_matches_reference() {
+                    let mut random = Random(0x9E3779B97F4A7C15);
+                    for _ in 0..1000 {
+                        let spec = CrcTable::new(
+                            (random.wide() as $t).max(1),
+                            random.wide() as $t,
+                            random.next() & 1 == 1,
+                            random.next() & 1 == 1,
+                            random.wide() as $t
+                        );
+                        assert_table_matches_reference(spec, &random.bytes(64));
+                    }
+                }
+
+                #[test]
+                fn table_matches_reference_any_width() {
+                    let mut random = Random(0x2545F4914F6CDD1D);
+                    let bits = ::core::mem::size_of::<$t>() * 8;
+                    for _ in 0..1000 {
+                        let width = 1 + random.next() as usize % bits;
+                        let mask = (!0 as $t) >> (bits - width);
+                        let spec = CrcTable::new_with_width(
+                            width,
+                            (random.wide() as $t & mask).max(1),
+                            random.wide() as $t & mask,
+                            random.next() & 1 == 1,
+                            random.next() & 1 == 1,
+                            random.wide() as $t & mask
+                        );
+                        assert_table_matches_reference(spec, &random.bytes(64));
+                    }
+                }
+            }
+        }
+    }
+
+    property_tests_for!(u8, test_u8);
+    property_tests_for!(u16, test_u16);
+    property_tests_for!(u32, test_u32);
+    property_tests_for!(u64, test_u64);
+    property_tests_for!(u128, test_u128);
+}
@@ -0,0 +1,155 @@
+use ::CrcSpec;
+use super::{ValueType, CrcTable, Table};
+
+
+/// A spec with a lookup table for a rolling CRC over a window of `window_len` bytes,
+/// as used in rsync-style content-defined chunking.
+///
+/// The rolling value is the raw register of the underlying spec, computed
+/// from a zero register, without `init`, the final reflection and `xorout`.
+/// That is, the value for a window is `window(bytes)`, not the checksum of the window.
+/// The rolling value can't be used with any other initial value, since that would make
+/// the contribution of the byte leaving the window depend on the whole history of the register.
+///
+/// ```
+/// use crc_rocksoft::primitive::*;
+///
+/// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+/// let rolling = CrcRollingTable::new(spec, 4);
+/// let data = b"abcdef";
+/// let value = rolling.window(&data[0..4]);
+/// let value = rolling.roll(value, data[0], data[4]);
+/// assert_eq!(value, rolling.window(&data[1..5]));
+/// ```
+pub struct CrcRollingTable<T> {
+    spec: CrcTable<T>,
+    window_len: usize,
+    out_table: Table<T>
+}
+
+impl<T: ValueType> CrcRollingTable<T> {
+
+    /// The constructor method. Computing the table takes time
+    /// proportional to `window_len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_len` is zero.
+    pub fn new(spec: CrcTable<T>, window_len: usize) -> Self {
+        assert!(window_len > 0, "the window must not be empty");
+        let zero = T::from(0);
+        // The entry at index `b` is the register value after processing the byte `b`
+        // followed by `window_len - 1` zero bytes. Thanks to linearity,
+        // only the entries for single-bit bytes have to be computed directly.
+        let mut out_table = [zero; 256];
+        for bit in 0..8 {
+            let b = 1u8 << bit;
+            out_table[b as usize] = (1..window_len).fold(spec.update(zero, b), |value, _| spec.update(value, 0));
+        }
+        for b in 1..256usize {
+            let low_bit = b & b.wrapping_neg();
+            if b != low_bit {
+                out_table[b] = out_table[b ^ low_bit] ^ out_table[low_bit];
+            }
+        }
+        CrcRollingTable { spec, window_len, out_table }
+    }
+
+    /// Returns the spec that this rolling spec was built from.
+    pub fn spec(&self) -> &CrcTable<T> {
+        &self.spec
+    }
+
+    /// Returns the length of the window in bytes.
+    pub fn window_len(&self) -> usize {
+        self.window_len
+    }
+
+    /// Computes the rolling value of `bytes` from scratch.
+    /// Usually, `bytes` is the first window of the data.
+    pub fn window(&self, bytes: &[u8]) -> T {
+        bytes.iter().fold(T::from(0), |value, &b| self.spec.update(value, b))
+    }
+
+    /// Slides the window by one byte: removes the byte `leaving` from the front of the window
+    /// whose rolling value is `value`, and appends the byte `entering` to its back.
+    pub fn roll(&self, value: T, leaving: u8, entering: u8) -> T {
+        self.spec.update(value ^ self.out_table[leaving as usize], entering)
+    }
+}
+
+impl<T: ValueType> CrcSpec<T> for CrcRollingTable<T> {
+    fn width(&self) -> usize { self.spec.width() }
+    fn poly(&self) -> T { self.spec.poly() }
+    fn init(&self) -> T { self.spec.init() }
+    fn refin(&self) -> bool { self.spec.refin() }
+    fn refout(&self) -> bool { self.spec.refout() }
+    fn xorout(&self) -> T { self.spec.xorout() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use primitive::test_util::Random;
+    use super::CrcRollingTable;
+    use super::super::{ValueType, CrcTable};
+
+    fn data() -> Vec<u8> {
+        Random(0x2545F4914F6CDD1D).data(300)
+    }
+
+    fn check_sliding<T: ValueType>(spec: CrcTable<T>) {
+        let data = data();
+        for &window_len in &[1, 2, 7, 16, 64] {
+            let rolling = CrcRollingTable::new(spec.clone(), window_len);
+            let mut value = rolling.window(&data[..window_len]);
+            for start in 1..=(data.len() - window_len) {
+                value = rolling.roll(value, data[start - 1], data[start + window_len - 1]);
+                assert_eq!(value, rolling.window(&data[start..start + window_len]), "window {}, at {}", window_len, start);
+            }
+        }
+    }
+
+    #[test]
+    fn crc32_iso_hdlc() {
+        check_sliding(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+    }
+
+    #[test]
+    fn crc32_bzip2() {
+        check_sliding(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32));
+    }
+
+    #[test]
+    fn crc16_modbus() {
+        check_sliding(CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16));
+    }
+
+    #[test]
+    fn crc8_smbus() {
+        check_sliding(CrcTable::new(0x07u8, 0u8, false, false, 0u8));
+    }
+
+    #[test]
+    fn sub_byte_widths() {
+        check_sliding(CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8));
+        check_sliding(CrcTable::new_with_width(5, 0x05u8, 0u8, false, false, 0u8));
+    }
+
+    #[test]
+    fn crc64_xz() {
+        check_sliding(CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64));
+    }
+
+    #[test]
+    fn crc82_darc() {
+        check_sliding(CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128));
+    }
+
+    #[test]
+    #[should_panic(expected = "the window must not be empty")]
+    fn empty_window() {
+        CrcRollingTable::new(CrcTable::new(0x07u8, 0u8, false, false, 0u8), 0);
+    }
+}
@@ -0,0 +1,227 @@
+//! Implementations of the `serde` traits for `CrcTable` and `CrcParams`.
+//! Only available with the `serde` feature.
+//!
+//! Only the parameters of the algorithm are serialized. The lookup table is computed
+//! anew on deserialization, so it doesn't take space in the serialized form,
+//! and a malformed table can't be smuggled in.
+//!
+//! In human-readable formats, such as JSON or TOML, the values of `poly`, `init` and `xorout`
+//! are deserialized either from integers or from hexadecimal strings like `"0x04C11DB7"`,
+//! since configuration files usually list them in hex, and some formats can't represent
+//! large 64-bit integers. Types wider than 64 bits are deserialized in their own way.
+
+use super::{ValueType, CrcTable, CrcParams};
+use super::parse::{parse_hex, from_u128};
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{Error, Unexpected, Visitor};
+
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CrcParams", bound(deserialize = "T: ValueType + Deserialize<'de>"))]
+struct Repr<T> {
+    width: usize,
+    #[serde(deserialize_with = "value")]
+    poly: T,
+    #[serde(deserialize_with = "value")]
+    init: T,
+    refin: bool,
+    refout: bool,
+    #[serde(deserialize_with = "value")]
+    xorout: T,
+}
+
+impl<T: ValueType + Serialize> Serialize for CrcParams<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Repr {
+            width: self.width,
+            poly: self.poly,
+            init: self.init,
+            refin: self.refin,
+            refout: self.refout,
+            xorout: self.xorout,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, T: ValueType + Deserialize<'de>> Deserialize<'de> for CrcParams<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let r = Repr::<T>::deserialize(deserializer)?;
+        Ok(CrcParams { width: r.width, poly: r.poly, init: r.init, refin: r.refin, refout: r.refout, xorout: r.xorout })
+    }
+}
+
+impl<T: ValueType + Serialize> Serialize for CrcTable<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params().serialize(serializer)
+    }
+}
+
+/// Fails if the parameters are invalid; see
+/// [`CrcTable::try_new_with_width`](struct.CrcTable.html#method.try_new_with_width).
+impl<'de, T: ValueType + Deserialize<'de>> Deserialize<'de> for CrcTable<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        CrcTable::try_from(CrcParams::<T>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+fn value<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where T: ValueType + Deserialize<'de>, D: Deserializer<'de>
+{
+    if deserializer.is_human_readable() && T::BITS <= 64 {
+        deserializer.deserialize_any(ValueVisitor(PhantomData))
+    } else {
+        T::deserialize(deserializer)
+    }
+}
+
+struct ValueVisitor<T>(PhantomData<T>);
+
+impl<'de, T: ValueType> Visitor<'de> for ValueVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an unsigned integer or a hexadecimal string of at most {} bits", T::BITS)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<T, E> {
+        from_u128(v as u128).ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<T, E> {
+        if v < 0 {
+            return Err(E::invalid_value(Unexpected::Signed(v), &self));
+        }
+        self.visit_u64(v as u64)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<T, E> {
+        parse_hex(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::CrcSpec;
+    use super::super::{CrcTable, CrcParams};
+    use serde_json;
+    use toml;
+    use core::convert::TryFrom;
+    use std::string::ToString;
+
+    macro_rules! round_trip_tests_for {
+        ($t:ty, $module:ident, $poly:expr) => {
+            mod $module {
+                use super::*;
+
+                #[test]
+                fn round_trip() {
+                    for &(refin, refout) in &[(false, false), (true, true), (true, false), (false, true)] {
+                        let spec = CrcTable::new($poly as $t, 3 as $t, refin, refout, 5 as $t);
+                        let json = serde_json::to_string(&spec).unwrap();
+                        let parsed: CrcTable<$t> = serde_json::from_str(&json).unwrap();
+                        assert_eq!(parsed.width(), spec.width());
+                        assert_eq!(parsed.poly(), spec.poly());
+                        assert_eq!(parsed.init(), spec.init());
+                        assert_eq!(parsed.refin(), spec.refin());
+                        assert_eq!(parsed.refout(), spec.refout());
+                        assert_eq!(parsed.xorout(), spec.xorout());
+                        assert_eq!(&parsed.table()[..], &spec.table()[..]);
+                    }
+                }
+            }
+        }
+    }
+
+    round_trip_tests_for!(u8, test_u8, 0x07);
+    round_trip_tests_for!(u16, test_u16, 0x8005);
+    round_trip_tests_for!(u32, test_u32, 0x04C11DB7);
+    round_trip_tests_for!(u64, test_u64, 0x42F0E1EBA9EA3693u64);
+    round_trip_tests_for!(u128, test_u128, 0x0308C0111011401440411u128);
+    round_trip_tests_for!(usize, test_usize, 0x04C11DB7);
+
+    #[test]
+    fn json_form() {
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_eq!(
+            serde_json::to_string(&spec).unwrap(),
+            r#"{"width":32,"poly":79764919,"init":4294967295,"refin":true,"refout":true,"xorout":4294967295}"#
+        );
+    }
+
+    #[test]
+    fn narrow_width() {
+        let json = r#"{"width":24,"poly":8801531,"init":11994318,"refin":false,"refout":false,"xorout":0}"#;
+        let spec: CrcTable<u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.check(), 0x21CF02);
+    }
+
+    #[test]
+    fn invalid_params() {
+        let json = r#"{"width":32,"poly":0,"init":0,"refin":false,"refout":false,"xorout":0}"#;
+        let err = serde_json::from_str::<CrcTable<u32>>(json).err().unwrap();
+        assert!(err.to_string().contains("poly must not be zero"), "{}", err);
+    }
+
+    #[test]
+    fn hex_strings() {
+        let json = r#"{"width":32,"poly":"0x04C11DB7","init":"0xffffffff","refin":true,"refout":true,"xorout":"ffffffff"}"#;
+        let spec: CrcTable<u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.check(), 0xCBF43926);
+        let json = r#"{"width":64,"poly":"0x42F0E1EBA9EA3693","init":"0xFFFFFFFFFFFFFFFF","refin":true,"refout":true,"xorout":18446744073709551615}"#;
+        let spec: CrcTable<u64> = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.check(), 0x995DC9BBDF1939FA);
+    }
+
+    #[test]
+    fn invalid_values() {
+        let parse = |poly: &str| {
+            let json = format!(r#"{{"width":16,"poly":{},"init":0,"refin":false,"refout":false,"xorout":0}}"#, poly);
+            serde_json::from_str::<CrcTable<u16>>(&json).err().unwrap().to_string()
+        };
+        assert!(parse("65536").contains("invalid value: integer `65536`"), "{}", parse("65536"));
+        assert!(parse("-1").contains("invalid value: integer `-1`"), "{}", parse("-1"));
+        assert!(parse(r#""0x10000""#).contains("invalid value: string \"0x10000\""), "{}", parse(r#""0x10000""#));
+        assert!(parse(r#""0x10g1""#).contains("hexadecimal string of at most 16 bits"), "{}", parse(r#""0x10g1""#));
+        assert!(parse("true").contains("invalid type: boolean"), "{}", parse("true"));
+    }
+
+    #[test]
+    fn params() {
+        let params = CrcParams { width: 16, poly: 0x1021u16, init: 0xFFFF, refin: false, refout: false, xorout: 0 };
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(json, r#"{"width":16,"poly":4129,"init":65535,"refin":false,"refout":false,"xorout":0}"#);
+        assert_eq!(serde_json::from_str::<CrcParams<u16>>(&json).unwrap(), params);
+        // Unlike CrcTable, CrcParams isn't validated.
+        let invalid = r#"{"width":16,"poly":0,"init":0,"refin":false,"refout":false,"xorout":0}"#;
+        assert_eq!(serde_json::from_str::<CrcParams<u16>>(invalid).unwrap().poly, 0);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        let text = toml::to_string(&spec).unwrap();
+        assert_eq!(text, "width = 32\npoly = 79764919\ninit = 4294967295\nrefin = true\nrefout = true\nxorout = 4294967295\n");
+        let parsed: CrcTable<u32> = toml::from_str(&text).unwrap();
+        assert!(parsed == spec);
+    }
+
+    #[test]
+    fn toml_hex_strings() {
+        let text = r#"
+            width = 64
+            poly = "0x42F0E1EBA9EA3693"
+            init = "0xFFFFFFFFFFFFFFFF"
+            refin = true
+            refout = true
+            xorout = "0xFFFFFFFFFFFFFFFF"
+        "#;
+        let spec: CrcTable<u64> = toml::from_str(text).unwrap();
+        assert_eq!(spec.check(), 0x995DC9BBDF1939FA);
+        let text = "width = 16\npoly = 0x1021\ninit = 0xFFFF\nrefin = false\nrefout = false\nxorout = 0\n";
+        let params: CrcParams<u16> = toml::from_str(text).unwrap();
+        assert_eq!(CrcTable::try_from(params).unwrap().check(), 0x29B1);
+    }
+}
@@ -0,0 +1,369 @@
+use ::{CrcSpec, CrcHasher, CrcBitInput};
+use super::{ValueType, CrcTable, Table};
+use core::borrow::Borrow;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")] use alloc::boxed::Box;
+#[cfg(feature = "alloc")] use core::convert::TryFrom;
+
+
+/// A spec with `N` lookup tables, which allows processing `N` bytes of a slice
+/// per step ("slicing-by-N") instead of one byte per step.
+///
+/// The first table is the usual lookup table of the spec; the `k`-th table contains
+/// the register values after processing a byte followed by `k` zero bytes.
+/// This makes the memory footprint `N` times that of `CrcTable`.
+///
+/// Note that non-reflected algorithms whose width is not a multiple of 8 bits
+/// don't benefit from the additional tables and are processed byte by byte.
+///
+/// `B` is the type of the additional tables, which is only different from the default
+/// for specs whose tables are on the heap; see [`new_boxed`](#method.new_boxed).
+pub struct CrcSlicedTable<T, const N: usize, B = [Table<T>; N]> {
+    spec: CrcTable<T>,
+    tables: B
+}
+
+/// A spec with four lookup tables, processing four bytes per step.
+/// It takes half the memory of `CrcSlice8Table`, which is typically faster.
+pub type CrcSlice4Table<T, B = [Table<T>; 4]> = CrcSlicedTable<T, 4, B>;
+
+/// A spec with eight lookup tables, processing eight bytes per step.
+pub type CrcSlice8Table<T, B = [Table<T>; 8]> = CrcSlicedTable<T, 8, B>;
+
+/// A spec with sixteen lookup tables, processing sixteen bytes per step.
+/// The tables take 16 KiB for `u32`, so on CPUs with small L1 caches
+/// it may well be slower than `CrcSlice8Table`; measure before choosing it.
+pub type CrcSlice16Table<T, B = [Table<T>; 16]> = CrcSlicedTable<T, 16, B>;
+
+impl<T: ValueType, const N: usize, B: Borrow<[Table<T>; N]>> CrcSlicedTable<T, N, B> {
+
+    /// Returns the single-table spec that this spec was built from.
+    pub fn spec(&self) -> &CrcTable<T> {
+        &self.spec
+    }
+
+    /// Updates a CRC register with all the bytes in the supplied slice,
+    /// taking into account this spec's `refin` value.
+    pub fn update_slice(&self, value: T, bytes: &[u8]) -> T {
+        let spec = &self.spec;
+        let width = spec.width();
+        if !spec.refin() && width % 8 != 0 {
+            return bytes.iter().fold(value, |value, &b| spec.update(value, b));
+        }
+        let mut value = value;
+        let mut chunks = bytes.chunks_exact(N);
+        for chunk in &mut chunks {
+            // The bits of the register that aren't consumed by the chunk.
+            let mut next = if spec.refin() {
+                value.checked_shr(N * 8).unwrap_or(T::from(0))
+            } else {
+                value.checked_shl(N * 8).map_or(T::from(0), |value| value & T::mask(width))
+            };
+            for (j, &byte) in chunk.iter().enumerate() {
+                let index = byte ^ self.register_byte(value, j);
+                next = next ^ self.tables.borrow()[N - 1 - j][index as usize];
+            }
+            value = next;
+        }
+        chunks.remainder().iter().fold(value, |value, &b| spec.update(value, b))
+    }
+
+    /// Returns the byte of the register that is combined with the `j`-th byte of a chunk.
+    fn register_byte(&self, value: T, j: usize) -> u8 {
+        let width = self.spec.width();
+        if self.spec.refin() {
+            if j * 8 < width { (value >> ((j * 8) as u8)).to_u8() } else { 0 }
+        } else if (j + 1) * 8 <= width {
+            (value >> ((width - (j + 1) * 8) as u8)).to_u8()
+        } else {
+            0
+        }
+    }
+
+}
+
+/// Fills the lookup tables for the spec: the `k`-th one is the `k-1`-th one
+/// followed by a zero byte.
+fn fill_tables<T: ValueType>(spec: &CrcTable<T>, tables: &mut [Table<T>]) {
+    tables[0] = *spec.table();
+    for k in 1..tables.len() {
+        let (done, rest) = tables.split_at_mut(k);
+        for (next, &prev) in rest[0].iter_mut().zip(done[k - 1].iter()) {
+            *next = spec.update(prev, 0);
+        }
+    }
+}
+
+impl<T: ValueType, const N: usize> From<CrcTable<T>> for CrcSlicedTable<T, N> {
+    /// Builds the additional lookup tables for the spec.
+    ///
+    /// The tables are built on the stack and moved with the spec, e. g. 16 KiB
+    /// for `CrcSlice8Table<u64>`; see [`new_boxed`](#method.new_boxed) for a spec
+    /// whose tables never are.
+    fn from(spec: CrcTable<T>) -> Self {
+        let mut tables = [[T::from(0); 256]; N];
+        fill_tables(&spec, &mut tables);
+        CrcSlicedTable { spec, tables }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ValueType, const N: usize> CrcSlicedTable<T, N, Box<[Table<T>; N]>> {
+    /// Builds the additional lookup tables for the spec directly on the heap.
+    /// Unlike [`from`](#impl-From%3CCrcTable%3CT%3E%3E-for-CrcSlicedTable%3CT,+N%3E),
+    /// this never has the tables on the stack, which matters for threads or tasks with small
+    /// stacks, and the spec can be moved, e. g. into a hasher, without copying them.
+    ///
+    /// Only available with the `alloc` feature.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let spec = CrcSlice16Table::new_boxed(CrcTable::new(0x42F0E1EBA9EA3693u64, !0, true, true, !0));
+    /// let mut hasher = CrcSlice16Hasher::from(spec);
+    /// hasher.update_from_slice(b"123456789");
+    /// assert_eq!(hasher.finish(), 0x995DC9BBDF1939FA);
+    /// ```
+    pub fn new_boxed(spec: CrcTable<T>) -> Self {
+        let zeros: Box<[Table<T>]> = (0..N).map(|_| [T::from(0); 256]).collect();
+        let mut tables = Box::<[Table<T>; N]>::try_from(zeros).unwrap_or_else(|_| unreachable!());
+        fill_tables(&spec, &mut *tables);
+        CrcSlicedTable { spec, tables }
+    }
+}
+
+impl<T: ValueType, const N: usize, B: Borrow<[Table<T>; N]>> CrcSpec<T> for CrcSlicedTable<T, N, B> {
+    fn width(&self) -> usize { self.spec.width() }
+    fn poly(&self) -> T { self.spec.poly() }
+    fn init(&self) -> T { self.spec.init() }
+    fn refin(&self) -> bool { self.spec.refin() }
+    fn refout(&self) -> bool { self.spec.refout() }
+    fn xorout(&self) -> T { self.spec.xorout() }
+}
+
+
+/// An implementation of `CrcHasher` backed by a `CrcSlicedTable`.
+/// `update_from_slice` processes `N` bytes per step; `update` works the same way as in
+/// [`CrcTableHasher`](struct.CrcTableHasher.html).
+///
+/// The tables take `N` times the memory of a `CrcTable`, e. g. 16 KiB for `CrcSlice8Table<u64>`,
+/// so when many hashers use the same spec, they should borrow it, or share an `Arc`,
+/// rather than own copies of it. A hasher that owns its spec should get one built with
+/// [`CrcSlicedTable::new_boxed`](struct.CrcSlicedTable.html#method.new_boxed),
+/// whose tables aren't copied when it's moved.
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+/// let mut hasher = CrcSlicedTableHasher::from(CrcSlice8Table::from(spec));
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), 0xCBF43926);
+/// ```
+pub struct CrcSlicedTableHasher<T, S: Borrow<CrcSlicedTable<T, N, B>>, const N: usize, B = [Table<T>; N]> {
+    value: T,
+    spec: S,
+    tables: PhantomData<B>
+}
+
+/// A hasher backed by a `CrcSlice4Table`.
+pub type CrcSlice4Hasher<T, S, B = [Table<T>; 4]> = CrcSlicedTableHasher<T, S, 4, B>;
+
+/// A hasher backed by a `CrcSlice8Table`.
+pub type CrcSlice8Hasher<T, S, B = [Table<T>; 8]> = CrcSlicedTableHasher<T, S, 8, B>;
+
+/// A hasher backed by a `CrcSlice16Table`.
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// let spec = CrcSlice16Table::from(CrcTable::new(0x1EDC6F41u32, !0, true, true, !0));
+/// let mut hasher = CrcSlice16Hasher::from(&spec);
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), 0xE3069283);
+/// ```
+pub type CrcSlice16Hasher<T, S, B = [Table<T>; 16]> = CrcSlicedTableHasher<T, S, 16, B>;
+
+impl<T: ValueType, S: Borrow<CrcSlicedTable<T, N, B>>, const N: usize, B: Borrow<[Table<T>; N]>> CrcHasher<T> for CrcSlicedTableHasher<T, S, N, B> {
+    fn reset(&mut self) {
+        self.value = self.spec.borrow().spec.start();
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.value = self.spec.borrow().spec.update(self.value, byte);
+    }
+
+    fn finish(&self) -> T {
+        self.spec.borrow().spec.finish(self.value)
+    }
+
+    fn update_from_slice(&mut self, bytes: &[u8]) {
+        self.value = self.spec.borrow().update_slice(self.value, bytes);
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcSlicedTable<T, N, B>>, const N: usize, B: Borrow<[Table<T>; N]>> CrcBitInput<T> for CrcSlicedTableHasher<T, S, N, B> {
+    fn update_bit(&mut self, bit: bool) {
+        self.value = self.spec.borrow().spec.update_bits(self.value, bit as u8, 1);
+    }
+
+    fn update_last_bits(&mut self, byte: u8, nbits: u8) {
+        self.value = self.spec.borrow().spec.update_last_bits(self.value, byte, nbits);
+    }
+
+    fn refin(&self) -> bool {
+        self.spec.borrow().refin()
+    }
+}
+
+impl<T: ValueType, S: Borrow<CrcSlicedTable<T, N, B>>, const N: usize, B: Borrow<[Table<T>; N]>> From<S> for CrcSlicedTableHasher<T, S, N, B> {
+    /// Constructs and returns a hasher from anything that can provide a reference to a spec.
+    fn from(spec_ref: S) -> Self {
+        let mut hasher = CrcSlicedTableHasher { value: T::from(0), spec: spec_ref, tables: PhantomData };
+        hasher.reset();
+        hasher
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use primitive::test_util::Random;
+    use ::CrcHasher;
+    use super::super::{ValueType, CrcTable, CrcTableHasher};
+    use super::{CrcSlicedTable, CrcSlicedTableHasher, CrcSlice16Table, CrcSlice16Hasher};
+
+    fn data() -> Vec<u8> {
+        Random(0x2545F4914F6CDD1D).data(1000)
+    }
+
+    fn assert_same<T: ValueType>(make: impl Fn() -> CrcTable<T>) {
+        assert_same_with::<T, 4>(&make);
+        assert_same_with::<T, 8>(&make);
+        assert_same_with::<T, 16>(&make);
+    }
+
+    fn assert_same_with<T: ValueType, const N: usize>(make: &impl Fn() -> CrcTable<T>) {
+        let data = data();
+        let sliced = CrcSlicedTable::<T, N>::from(make());
+        let spec = make();
+        // All the lengths up to a couple of chunks, to cover every remainder.
+        for len in (0..40).chain(Some(data.len())) {
+            let mut expected = CrcTableHasher::from(&spec);
+            expected.update_from_slice(&data[..len]);
+            let mut actual = CrcSlicedTableHasher::from(&sliced);
+            actual.update_from_slice(&data[..len]);
+            assert_eq!(actual.finish(), expected.finish(), "N: {}, len: {}", N, len);
+        }
+    }
+
+    #[test]
+    fn crc8() {
+        assert_same(|| CrcTable::new(0x07u8, 0u8, false, false, 0u8));
+        assert_same(|| CrcTable::new(0x31u8, 0u8, true, true, 0u8));
+    }
+
+    #[test]
+    fn crc16() {
+        assert_same(|| CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16));
+        assert_same(|| CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16));
+    }
+
+    #[test]
+    fn crc32() {
+        assert_same(|| CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32));
+        assert_same(|| CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+    }
+
+    #[test]
+    fn crc64() {
+        assert_same(|| CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, false, false, !0u64));
+        assert_same(|| CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64));
+    }
+
+    #[test]
+    fn sub_width() {
+        assert_same(|| CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32));
+        assert_same(|| CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, true, true, 0u32));
+        assert_same(|| CrcTable::new_with_width(12, 0x80Fu16, 0u16, false, true, 0u16));
+        assert_same(|| CrcTable::new_with_width(12, 0x80Fu16, 0u16, true, false, 0u16));
+    }
+
+    #[test]
+    fn sub_byte_width() {
+        assert_same(|| CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8));
+        assert_same(|| CrcTable::new_with_width(7, 0x09u8, 0u8, false, false, 0u8));
+    }
+
+    #[test]
+    fn wider_than_chunk() {
+        assert_same(|| CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128));
+        assert_same(|| CrcTable::new_with_width(88, 0x0308C0111011401440411u128, 0u128, false, false, 0u128));
+    }
+
+    #[test]
+    fn catalog_entries() {
+        use catalog::{all, CatalogSpec};
+        for entry in all() {
+            match entry.spec() {
+                CatalogSpec::U8(spec) => assert_same(|| spec.clone()),
+                CatalogSpec::U16(spec) => assert_same(|| spec.clone()),
+                CatalogSpec::U32(spec) => assert_same(|| spec.clone()),
+                CatalogSpec::U64(spec) => assert_same(|| spec.clone()),
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_identical_to_from() {
+        let spec = || CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, true, true, 0u32);
+        let boxed = CrcSlicedTable::<u32, 8, _>::new_boxed(spec());
+        let sliced = CrcSlicedTable::<u32, 8>::from(spec());
+        assert_eq!(&boxed.tables[..], &sliced.tables[..]);
+        assert!(*boxed.spec() == *sliced.spec());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn small_stack() {
+        use std::thread;
+        // The tables alone take 32 KiB, twice this stack.
+        let hash = thread::Builder::new().stack_size(16 * 1024).spawn(|| {
+            let spec = CrcSlice16Table::new_boxed(CrcTable::new(0x42F0E1EBA9EA3693u64, !0, true, true, !0));
+            let mut hasher = CrcSlice16Hasher::from(spec);
+            hasher.update_from_slice(b"123456789");
+            hasher.finish()
+        }).unwrap().join().unwrap();
+        assert_eq!(hash, 0x995DC9BBDF1939FA);
+    }
+
+    /// Multi-megabyte messages, as in bulk hashing, against the bitwise reference.
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn large_buffers() {
+        use primitive::reference::reference_crc;
+        let data = Random(0x9E3779B97F4A7C15).data(2 << 20);
+        for &refin in &[false, true] {
+            let crc32 = CrcTable::new(0x04C11DB7u32, !0u32, refin, refin, !0u32);
+            let sliced = CrcSlicedTable::<u32, 8, _>::new_boxed(crc32.clone());
+            let mut hasher = CrcSlicedTableHasher::from(&sliced);
+            // An odd split, so that the chunks of the second call aren't aligned.
+            hasher.update_from_slice(&data[..12345]);
+            hasher.update_from_slice(&data[12345..]);
+            assert_eq!(hasher.finish(), reference_crc(&crc32, &data), "refin: {}", refin);
+
+            let crc64 = CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, refin, refin, !0u64);
+            let mut hasher = CrcSlicedTableHasher::from(CrcSlicedTable::<u64, 8, _>::new_boxed(crc64.clone()));
+            hasher.update_from_slice(&data[1..]);
+            assert_eq!(hasher.finish(), reference_crc(&crc64, &data[1..]), "refin: {}", refin);
+
+            let mut hasher = CrcSlice16Hasher::from(CrcSlice16Table::new_boxed(crc64.clone()));
+            hasher.update_from_slice(&data[3..]);
+            assert_eq!(hasher.finish(), reference_crc(&crc64, &data[3..]), "refin: {}", refin);
+        }
+    }
+}
@@ -1,57 +1,626 @@
-use ::CrcSpec;
-use super::{ValueType, Table, fill_table};
-use std::mem::size_of;
+use ::{CrcSpec, CrcHasher};
+use super::{ValueType, CrcParams, CrcError, TableMismatch, Table, find_mismatch, CrcTableHasher, RegisterBytes, fill_table, reflect};
+use super::{const_table_u8, const_table_u16, const_table_u32, const_table_u64, const_table_u128, const_table_usize};
+use super::poly::{from_koopman, reversed, reciprocal};
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use interop::ByteOrder;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+#[cfg(feature = "alloc")] use alloc::boxed::Box;
+#[cfg(feature = "alloc")] use core::convert::TryFrom;
 
 
 /// An implementation of `CrcSpec` with a lookup table (for performance optimization) embedded in it.
 ///
-/// The embedded table is of type `[T; 256]`.
-pub struct CrcTable<T> {
+/// The embedded table is of type `[T; 256]`. A spec constructed with
+/// [`from_parts`](#method.from_parts) borrows a `&'static [T; 256]` instead, e. g. one placed
+/// in flash memory; `B` is the type of the table, which all the computations borrow from.
+#[derive(Clone)]
+pub struct CrcTable<T, B = Table<T>> {
+    width: usize,
     poly: T,
     init: T,
     refin: bool,
     refout: bool,
     xorout: T,
 
-    table: Table<T>
+    /// The initial value of the register, i. e. `init` reflected if `refin` is set.
+    start: T,
+    table: B
 }
 
 impl<T: ValueType> CrcTable<T> {
 
-    /// The constructor method.
+    /// The constructor method. The width of the algorithm is the
+    /// [default width](trait.ValueType.html#method.default_width) of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
     pub fn new(poly: T, init: T, refin: bool, refout: bool, xorout: T) -> CrcTable<T> {
+        CrcTable::new_with_width(T::default_width(), poly, init, refin, refout, xorout)
+    }
+
+    /// The constructor method for algorithms whose width is less than the bit size of `T`,
+    /// e. g. CRC-24 backed by `u32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new_with_width`](#method.try_new_with_width).
+    pub fn new_with_width(width: usize, poly: T, init: T, refin: bool, refout: bool, xorout: T) -> CrcTable<T> {
+        CrcTable::try_new_with_width(width, poly, init, refin, refout, xorout)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// The constructor method for polynomials in Koopman notation, as found in Koopman's
+    /// polynomial tables. The width of the algorithm is the
+    /// [default width](trait.ValueType.html#method.default_width) of `T`;
+    /// for other widths, convert the polynomial with
+    /// [`poly::from_koopman`](poly/fn.from_koopman.html) and use `new_with_width`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    pub fn new_koopman(koopman_poly: T, init: T, refin: bool, refout: bool, xorout: T) -> CrcTable<T> {
+        let poly = from_koopman(koopman_poly, T::default_width());
+        CrcTable::new(poly, init, refin, refout, xorout)
+    }
+
+    /// The constructor method for polynomials in the reversed notation, e. g. `0xEDB88320`
+    /// for CRC-32. The width of the algorithm is the
+    /// [default width](trait.ValueType.html#method.default_width) of `T`;
+    /// for other widths, convert the polynomial with
+    /// [`poly::reversed`](poly/fn.reversed.html) and use `new_with_width`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    pub fn new_reversed(reversed_poly: T, init: T, refin: bool, refout: bool, xorout: T) -> CrcTable<T> {
+        let poly = reversed(reversed_poly, T::default_width());
+        CrcTable::new(poly, init, refin, refout, xorout)
+    }
+
+    /// The fallible constructor method. The width of the algorithm is the
+    /// [default width](trait.ValueType.html#method.default_width) of `T`.
+    ///
+    /// Returns an error if the poly is zero.
+    pub fn try_new(poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Result<CrcTable<T>, CrcError<T>> {
+        CrcTable::try_new_with_width(T::default_width(), poly, init, refin, refout, xorout)
+    }
+
+    /// The fallible constructor method for algorithms whose width is less than the bit size of `T`.
+    ///
+    /// Returns an error if `width` is zero or greater than the bit size of `T`,
+    /// if the poly is zero, or if any of `poly`, `init` and `xorout` doesn't fit in `width` bits.
+    pub fn try_new_with_width(width: usize, poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Result<CrcTable<T>, CrcError<T>> {
+        validate(width, poly, init, xorout)?;
         let mut spec = CrcTable {
-            poly: poly,
-            init: init,
-            refin: refin,
-            refout: refout,
-            xorout: xorout,
+            width,
+            poly,
+            init,
+            refin,
+            refout,
+            xorout,
+            start: start_value(init, width, refin),
             table: [T::from(0); 256]
         };
-        fill_table(&mut spec.table, poly, refin);
+        fill_table(&mut spec.table, poly, width, refin);
+        Ok(spec)
+    }
+
+    /// The constructor method for a table computed in advance, e. g. at compile time
+    /// with one of the `const_table_*` functions. The width of the algorithm is the
+    /// [default width](trait.ValueType.html#method.default_width) of `T`.
+    /// If the whole spec can be a `static`, see `new_const`.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// static CRC32_TABLE: Table<u32> = const_table_u32(0x04C11DB7, 32, true);
+    ///
+    /// let spec = CrcTable::from_const_table(0x04C11DB7, 0xFFFFFFFF, true, true, 0xFFFFFFFF, &CRC32_TABLE);
+    /// assert_eq!(spec.check(), 0xCBF43926);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    /// The table must have been computed for the same `poly`, width and `refin`;
+    /// this is only verified in debug builds.
+    pub fn from_const_table(poly: T, init: T, refin: bool, refout: bool, xorout: T, table: &Table<T>) -> CrcTable<T> {
+        CrcTable::from_const_table_with_width(T::default_width(), poly, init, refin, refout, xorout, table)
+    }
+
+    /// The same as [`from_const_table`](#method.from_const_table), but for algorithms
+    /// whose width is less than the bit size of `T`.
+    pub fn from_const_table_with_width(width: usize, poly: T, init: T, refin: bool, refout: bool, xorout: T, table: &Table<T>) -> CrcTable<T> {
+        validate(width, poly, init, xorout).unwrap_or_else(|e| panic!("{}", e));
+        let start = start_value(init, width, refin);
+        let spec = CrcTable { width, poly, init, refin, refout, xorout, start, table: *table };
+        debug_assert!(
+            {
+                let mut expected = [T::from(0); 256];
+                fill_table(&mut expected, poly, width, refin);
+                expected[..] == spec.table[..]
+            },
+            "the table doesn't match the parameters"
+        );
         spec
     }
 
+    /// Returns the same algorithm with a different `init`. The lookup table is reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `init` doesn't fit in the width of the algorithm.
+    pub fn with_init(mut self, init: T) -> CrcTable<T> {
+        validate(self.width, self.poly, init, self.xorout).unwrap_or_else(|e| panic!("{}", e));
+        self.init = init;
+        self.start = start_value(init, self.width, self.refin);
+        self
+    }
+
+    /// Returns the same algorithm with a different `xorout`. The lookup table is reused.
+    ///
+    /// JAMCRC, for example, is CRC-32 without the final inversion:
+    ///
+    /// ```
+    /// use crc_rocksoft::catalog;
+    ///
+    /// assert_eq!(catalog::crc32_iso_hdlc().with_xorout(0).check(), catalog::crc32_jamcrc().check());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xorout` doesn't fit in the width of the algorithm.
+    pub fn with_xorout(mut self, xorout: T) -> CrcTable<T> {
+        validate(self.width, self.poly, self.init, xorout).unwrap_or_else(|e| panic!("{}", e));
+        self.xorout = xorout;
+        self
+    }
+
+    /// Returns the same algorithm with a different `refin`.
+    /// The lookup table is computed anew if `refin` changes, since it depends on it.
+    ///
+    /// ```
+    /// use crc_rocksoft::catalog;
+    ///
+    /// let bzip2 = catalog::crc32_iso_hdlc().with_refin(false).with_refout(false);
+    /// assert_eq!(bzip2.check(), catalog::crc32_bzip2().check());
+    /// ```
+    pub fn with_refin(mut self, refin: bool) -> CrcTable<T> {
+        if refin != self.refin {
+            self.refin = refin;
+            self.start = start_value(self.init, self.width, refin);
+            fill_table(&mut self.table, self.poly, self.width, refin);
+        }
+        self
+    }
+
+    /// Returns the same algorithm with a different `refout`. The lookup table is reused.
+    pub fn with_refout(mut self, refout: bool) -> CrcTable<T> {
+        self.refout = refout;
+        self
+    }
+
+}
+
+impl<T: ValueType> CrcTable<T, &'static Table<T>> {
+    /// Constructs a spec that borrows a lookup table computed in advance, e. g. one generated
+    /// offline and placed in a dedicated section of flash memory, instead of embedding a copy.
+    /// The table is checked against the parameters; see `find_mismatch` in `table.rs`.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// static CRC32_TABLE: Table<u32> = const_table_u32(0x04C11DB7, 32, true);
+    ///
+    /// let params = CrcParams { width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF };
+    /// let spec = CrcTable::from_parts(params, &CRC32_TABLE).unwrap();
+    /// assert!(std::ptr::eq(spec.table(), &CRC32_TABLE));
+    ///
+    /// let mut hasher = CrcTableHasher::from(&spec);
+    /// hasher.update_from_slice(b"123456789");
+    /// assert_eq!(hasher.finish(), 0xCBF43926);
+    /// ```
+    ///
+    /// Returns an error naming the first byte whose entry doesn't match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    pub fn from_parts(params: CrcParams<T>, table: &'static Table<T>) -> Result<Self, TableMismatch> {
+        let CrcParams { width, poly, init, refin, refout, xorout } = params;
+        validate(width, poly, init, xorout).unwrap_or_else(|e| panic!("{}", e));
+        find_mismatch(&params, table).map_or(Ok(()), |index| Err(TableMismatch { index }))?;
+        Ok(CrcTable { width, poly, init, refin, refout, xorout, start: start_value(init, width, refin), table })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ValueType> CrcTable<T, Box<Table<T>>> {
+    /// The same as [`new`](#method.new), but the lookup table is allocated on the heap.
+    /// A `CrcTable` embeds its lookup table, e. g. 2 KiB for `u64`, which is copied whenever
+    /// the spec is moved; the table of this spec is never on the stack, and the spec itself
+    /// is only as large as the parameters and a pointer, which matters for threads or tasks
+    /// with small stacks. Hashers constructed from it with
+    /// [`CrcTableHasher::from`](struct.CrcTableHasher.html#impl-From%3CS%3E-for-CrcTableHasher%3CT,+S,+B%3E)
+    /// don't copy the table either.
+    ///
+    /// Only available with the `alloc` feature.
+    ///
+    /// ```
+    /// use crc_rocksoft::*;
+    /// use crc_rocksoft::primitive::*;
+    ///
+    /// let spec = CrcTable::new_boxed(0x42F0E1EBA9EA3693u64, !0, true, true, !0);
+    /// let mut hasher = CrcTableHasher::from(spec);
+    /// hasher.update_from_slice(b"123456789");
+    /// assert_eq!(hasher.finish(), 0x995DC9BBDF1939FA);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see [`try_new`](#method.try_new).
+    pub fn new_boxed(poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Self {
+        CrcTable::new_boxed_with_width(T::default_width(), poly, init, refin, refout, xorout)
+    }
+
+    /// The same as [`new_boxed`](#method.new_boxed), but for algorithms
+    /// whose width is less than the bit size of `T`.
+    pub fn new_boxed_with_width(width: usize, poly: T, init: T, refin: bool, refout: bool, xorout: T) -> Self {
+        validate(width, poly, init, xorout).unwrap_or_else(|e| panic!("{}", e));
+        let zeros: Box<[T]> = (0..256).map(|_| T::from(0)).collect();
+        let mut table = Box::<Table<T>>::try_from(zeros).unwrap_or_else(|_| unreachable!());
+        fill_table(&mut table, poly, width, refin);
+        CrcTable { width, poly, init, refin, refout, xorout, start: start_value(init, width, refin), table }
+    }
+}
+
+impl<T: ValueType, B: Borrow<Table<T>>> CrcTable<T, B> {
+    /// Returns the lookup table used by this spec.
+    /// See [`fill_table`](fn.fill_table.html) for the layout.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// // The first row of zlib's table.
+    /// for entry in &crc32.table()[..8] {
+    ///     print!("0x{:08x}, ", entry);
+    /// }
+    /// println!();
+    /// assert_eq!(crc32.table()[1], 0x77073096);
+    /// ```
+    pub fn table(&self) -> &Table<T> {
+        self.table.borrow()
+    }
+
+    /// Returns the initial value of a CRC register,
+    /// taking into account this spec's `refin` value.
+    /// It's computed once, when the spec is constructed, so resetting a hasher is a copy.
+    pub fn start(&self) -> T {
+        self.start
+    }
+
     /// Updates a CRC register with one byte of user data,
     /// taking into account this spec's `refin` value.
     pub fn update(&self, value: T, byte: u8) -> T {
-        if self.refin {
-            (value >> 8) ^ self.table[(value.to_u8() ^ byte) as usize]
-        } else {
-            (value << 8) ^ self.table[((value >> 24).to_u8() ^ byte) as usize]
+        self.update_with_width(self.width, value, byte)
+    }
+
+    /// Updates the CRC register `value` with all the bytes of `data` and returns it
+    /// without applying the final stages, i. e. before [`finish`](#method.finish).
+    /// `value` is a register value as returned by [`start`](#method.start) or by this method,
+    /// so a message can be processed in several calls.
+    ///
+    /// This is the inner loop of the bytewise hashers, without the indirection of a hasher.
+    /// With the `hw` feature, it uses the CPU's CRC instructions for the algorithms
+    /// that they support, such as CRC-32C on x86-64 CPUs with SSE4.2, or carry-less
+    /// multiplication for the reflected CRC-32 poly on x86-64 CPUs with PCLMULQDQ.
+    /// On AArch64, both are computed with the instructions of the CRC extension.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// let value = crc32.checksum_with_state(crc32.start(), b"1234");
+    /// let value = crc32.checksum_with_state(value, b"56789");
+    /// assert_eq!(crc32.finish(value), 0xCBF43926);
+    /// ```
+    pub fn checksum_with_state(&self, value: T, data: &[u8]) -> T {
+        #[cfg(feature = "hw")]
+        {
+            if let Some(value) = super::hw::checksum_with_state(self, value, data) {
+                return value;
+            }
+        }
+        if self.refin && T::BITS >= 32 {
+            return self.checksum_words(value, data);
+        }
+        data.iter().fold(value, |value, &byte| self.update(value, byte))
+    }
+
+    /// The implementation of `checksum_with_state` for reflected algorithms, which processes
+    /// the data a little-endian 32-bit word at a time. The register is updated with the bytes
+    /// of a word by XORing the whole word into it, followed by four table lookups,
+    /// since the register of a reflected algorithm consumes its low bytes first.
+    /// The bytes after the last whole word are processed one at a time.
+    fn checksum_words(&self, mut value: T, data: &[u8]) -> T {
+        let mut words = data.chunks_exact(4);
+        for word in &mut words {
+            value = value ^ T::from(word[0]) ^ (T::from(word[1]) << 8) ^ (T::from(word[2]) << 16) ^ (T::from(word[3]) << 24);
+            for _ in 0..4 {
+                value = (value >> 8) ^ self.table()[value.to_u8() as usize];
+            }
         }
+        words.remainder().iter().fold(value, |value, &byte| self.update(value, byte))
     }
 
     /// Applies the REFOUT and XOROUT stages to the supplied CRC register value,
     /// returning the resulting checksum.
     pub fn finish(&self, value: T) -> T {
-        (if self.refin != self.refout { value.swap_bits() } else { value }) ^ self.xorout
+        self.finish_with_width(self.width, value)
+    }
+
+    /// The same as [`finish`](#method.finish), but returns the checksum as bytes,
+    /// most significant byte first. There are as many bytes as needed to fit the width
+    /// of the algorithm, e. g. three for CRC-24 even though it's backed by `u32`.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+    /// let mut value = crc24.start();
+    /// for &b in b"123456789" {
+    ///     value = crc24.update(value, b);
+    /// }
+    /// assert_eq!(&*crc24.finish_be_bytes(value), &[0x21, 0xCF, 0x02]);
+    /// ```
+    pub fn finish_be_bytes(&self, value: T) -> RegisterBytes {
+        self.finish(value).be_bytes(self.width)
+    }
+
+    /// The same as [`finish_be_bytes`](#method.finish_be_bytes), but the bytes are
+    /// least significant byte first.
+    pub fn finish_le_bytes(&self, value: T) -> RegisterBytes {
+        self.finish(value).le_bytes(self.width)
+    }
+
+    /// Computes the checksum of the contents of `buf` and appends it in the given byte order,
+    /// as many bytes as [`finish_be_bytes`](#method.finish_be_bytes) returns.
+    ///
+    /// Only available with the `alloc` feature.
+    ///
+    /// ```
+    /// use crc_rocksoft::interop::ByteOrder;
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc16 = CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16);
+    /// let mut frame = b"123456789".to_vec();
+    /// crc16.append_checksum(&mut frame, ByteOrder::LittleEndian);
+    /// assert_eq!(&frame[9..], &[0x37, 0x4B]);
+    /// assert_eq!(crc16.strip_and_verify(&frame, ByteOrder::LittleEndian), Some(&b"123456789"[..]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn append_checksum(&self, buf: &mut Vec<u8>, order: ByteOrder) {
+        let bytes = self.checksum_bytes(buf, order);
+        buf.extend_from_slice(&bytes);
+    }
+
+    /// Splits the checksum trailer off `buf`, in the given byte order, and checks it
+    /// against the checksum of the rest. Returns the rest if the checksums match,
+    /// and `None` if they don't or if `buf` is too short to have a trailer.
+    pub fn strip_and_verify<'a>(&self, buf: &'a [u8], order: ByteOrder) -> Option<&'a [u8]> {
+        let len = self.width.div_ceil(8);
+        if buf.len() < len {
+            return None;
+        }
+        let (data, trailer) = buf.split_at(buf.len() - len);
+        if *self.checksum_bytes(data, order) == *trailer { Some(data) } else { None }
+    }
+
+    fn checksum_bytes(&self, data: &[u8], order: ByteOrder) -> RegisterBytes {
+        let value = self.checksum_with_state(self.start(), data);
+        match order {
+            ByteOrder::BigEndian => self.finish_be_bytes(value),
+            ByteOrder::LittleEndian => self.finish_le_bytes(value),
+        }
+    }
+
+    /// Updates the CRC register `value` with the low `nbits` bits of `bits`, one bit at a time,
+    /// without using the lookup table. This is meant for the final partial byte of messages
+    /// whose length in bits is not a multiple of 8.
+    ///
+    /// The bits are processed in the same order as those of a whole byte: starting from
+    /// the most significant of the `nbits` bits, or from the least significant one
+    /// if `refin` is set. So, `update_bits(value, byte, 8)` is the same as `update(value, byte)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbits` is greater than 8.
+    pub fn update_bits(&self, value: T, bits: u8, nbits: usize) -> T {
+        update_bits(self, value, bits, nbits)
+    }
+
+    /// Updates the CRC register `value` with the first `nbits` bits of `byte`, i. e. the ones
+    /// `update` would process first: the most significant bits, or the least significant ones
+    /// if `refin` is set. This is meant for a final byte of which only `nbits` bits are meaningful,
+    /// as produced by hardware that shifts the message one bit at a time.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let spec = CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16);
+    /// // Only the top three bits (101) of the last byte are part of the message.
+    /// let value = spec.update(spec.start(), 0x31);
+    /// assert_eq!(spec.update_last_bits(value, 0b1010_0000, 3), spec.update_bits(value, 0b101, 3));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbits` is greater than 8.
+    pub fn update_last_bits(&self, value: T, byte: u8, nbits: u8) -> T {
+        update_last_bits(self, value, byte, nbits)
+    }
+
+    /// Returns the poly of this spec in the reversed notation.
+    /// The same as [`CrcSpec::reflected_poly`](../trait.CrcSpec.html#method.reflected_poly).
+    pub fn reversed_poly(&self) -> T {
+        reversed(self.poly, self.width)
+    }
+
+    /// Returns the spec that differs from this one only in having
+    /// the [reciprocal](poly/fn.reciprocal.html) poly.
+    pub fn reciprocal(&self) -> CrcTable<T> {
+        let poly = reciprocal(self.poly, self.width);
+        CrcTable::new_with_width(self.width, poly, self.init, self.refin, self.refout, self.xorout)
+    }
+
+    /// Returns the check value of the algorithm: the checksum of the ASCII string `"123456789"`,
+    /// as listed in CRC catalogues.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// assert_eq!(crc32.check(), 0xCBF43926);
+    /// ```
+    pub fn check(&self) -> T {
+        let mut hasher = CrcTableHasher::from(self);
+        hasher.update_from_slice(b"123456789");
+        hasher.finish()
+    }
+
+    /// Returns the residue of the algorithm: the value of the register after processing
+    /// an error-free message followed by its checksum, before the XOROUT stage
+    /// (but after reflection, if `refout` is set).
+    ///
+    /// It is computed without a message: the register is set to `xorout`
+    /// (reflected if `refout` is set), and then `width` zero bits are processed.
+    pub fn residue(&self) -> T {
+        let zero = T::from(0);
+        let top_bit_mask = T::from(1) << ((self.width - 1) as u8);
+        let mask = T::mask(self.width);
+        let mut value = if self.refout { reflect(self.xorout, self.width) } else { self.xorout };
+        for _ in 0..self.width {
+            let feedback = (value & top_bit_mask) != zero;
+            value = (value << 1) & mask;
+            if feedback {
+                value = value ^ self.poly;
+            }
+        }
+        if self.refout { reflect(value, self.width) } else { value }
+    }
+
+    /// Checks whether the checksum of `data` is `expected`.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// assert!(crc32.verify(b"123456789", 0xCBF43926));
+    /// ```
+    pub fn verify(&self, data: &[u8], expected: T) -> bool {
+        let mut hasher = CrcTableHasher::from(self);
+        hasher.update_from_slice(data);
+        hasher.finish() == expected
+    }
+
+    /// Checks a message followed by its checksum, without splitting off the trailer:
+    /// the whole of `data_with_crc` is processed, and the register is compared
+    /// with the [residue](#method.residue).
+    ///
+    /// The checksum must be appended in the order the algorithm processes bits:
+    /// least significant byte first if `refout` is set, most significant byte first otherwise.
+    /// This only works for algorithms whose width is a multiple of 8 and whose
+    /// `refin` and `refout` are equal.
+    ///
+    /// ```
+    /// use crc_rocksoft::primitive::CrcTable;
+    ///
+    /// let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+    /// assert!(crc32.verify_residue(b"123456789\x26\x39\xF4\xCB"));
+    /// ```
+    pub fn verify_residue(&self, data_with_crc: &[u8]) -> bool {
+        let value = self.checksum_with_state(self.start(), data_with_crc);
+        self.finish(value) ^ self.xorout == self.residue()
+    }
+
+    /// Computes the checksum of the concatenation of two messages A and B
+    /// from the checksums of the messages and the length of B in bytes,
+    /// without processing any of the messages' data.
+    ///
+    /// Like zlib's `crc32_combine`, this advances the register state of A
+    /// over `len_b` zero bytes using the GF(2) matrix of the "advance one bit" operator,
+    /// raised to the required power by repeated squaring.
+    pub fn combine(&self, crc_a: T, crc_b: T, len_b: usize) -> T {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let output = |value: T| if self.refout { reflect(value, self.width) } else { value };
+
+        // The (unreflected) difference between A's register and the initial register.
+        // This is what the register of A||B differs by from the register of B, once advanced.
+        let mut diff = output(crc_a ^ self.xorout) ^ self.init;
+        if len_b == 0 || diff == zero {
+            return crc_b ^ output(diff);
+        }
+
+        // Column `i` of a matrix is the image of the register value with only the bit `i` set.
+        let mut matrix = [zero; MAX_BITS];
+        let mut squared = [zero; MAX_BITS];
+        for (i, column) in matrix[..self.width - 1].iter_mut().enumerate() {
+            *column = one << ((i + 1) as u8);
+        }
+        matrix[self.width - 1] = self.poly;
+        // One bit -> two bits -> four bits -> one byte
+        for _ in 0..3 {
+            gf2_matrix_square(&mut squared[..self.width], &matrix[..self.width]);
+            ::core::mem::swap(&mut matrix, &mut squared);
+        }
+
+        let mut len = len_b;
+        loop {
+            if len & 1 != 0 {
+                diff = gf2_matrix_times(&matrix[..self.width], diff);
+            }
+            len >>= 1;
+            if len == 0 {
+                break;
+            }
+            gf2_matrix_square(&mut squared[..self.width], &matrix[..self.width]);
+            ::core::mem::swap(&mut matrix, &mut squared);
+        }
+        crc_b ^ output(diff)
+    }
+
+    /// The implementation of `update`, for callers that know the width at compile time.
+    #[inline(always)]
+    pub(super) fn update_with_width(&self, width: usize, value: T, byte: u8) -> T {
+        if width <= 8 {
+            // The whole register is shifted out by the byte, regardless of the direction.
+            let aligned = if self.refin { value } else { value << ((8 - width) as u8) };
+            self.table()[(aligned.to_u8() ^ byte) as usize]
+        } else if self.refin {
+            (value >> 8) ^ self.table()[(value.to_u8() ^ byte) as usize]
+        } else {
+            let index = (value >> ((width - 8) as u8)).to_u8() ^ byte;
+            ((value << 8) ^ self.table()[index as usize]) & T::mask(width)
+        }
+    }
+
+    /// The implementation of `finish`, for callers that know the width at compile time.
+    #[inline(always)]
+    pub(super) fn finish_with_width(&self, width: usize, value: T) -> T {
+        let value = value & T::mask(width);
+        (if self.refin != self.refout { reflect(value, width) } else { value }) ^ self.xorout
     }
 
 }
 
-impl<T: ValueType> CrcSpec<T> for CrcTable<T> {
-    fn width(&self) -> usize { size_of::<T>() * 8 }
+impl<T: ValueType, B: Borrow<Table<T>>> CrcSpec<T> for CrcTable<T, B> {
+    fn width(&self) -> usize { self.width }
     fn poly(&self) -> T { self.poly }
     fn init(&self) -> T { self.init }
     fn refin(&self) -> bool { self.refin }
@@ -59,6 +628,209 @@ impl<T: ValueType> CrcSpec<T> for CrcTable<T> {
     fn xorout(&self) -> T { self.xorout }
 }
 
+/// Specs are equal if their parameters are; the lookup table is derived from them.
+impl<T: ValueType, B> PartialEq for CrcTable<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.poly == other.poly && self.init == other.init
+            && self.refin == other.refin && self.refout == other.refout && self.xorout == other.xorout
+    }
+}
+
+impl<T: ValueType + Eq, B> Eq for CrcTable<T, B> {}
+
+/// Only the parameters are hashed, consistently with `PartialEq`.
+impl<T: ValueType + Hash, B> Hash for CrcTable<T, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.poly.hash(state);
+        self.init.hash(state);
+        self.refin.hash(state);
+        self.refout.hash(state);
+        self.xorout.hash(state);
+    }
+}
+
+macro_rules! impl_new_const {
+    ($t:ty, $table:ident, $default_width:expr) => {
+        impl CrcTable<$t> {
+            /// A `const fn` version of [`new`](#method.new), which computes the lookup table
+            /// at compile time when used in a `static` or a `const`:
+            ///
+            /// ```
+            /// use crc_rocksoft::primitive::CrcTable;
+            ///
+            #[doc = concat!("static SPEC: CrcTable<", stringify!($t), "> = CrcTable::<", stringify!($t), ">::new_const(0x07, 0, false, false, 0);")]
+            /// assert!(SPEC == CrcTable::new(0x07, 0, false, false, 0));
+            /// assert_eq!(SPEC.table()[..], CrcTable::new(0x07, 0, false, false, 0).table()[..]);
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// Panics if the parameters are invalid, which is a compile-time error
+            /// in a constant context; see [`try_new`](#method.try_new).
+            pub const fn new_const(poly: $t, init: $t, refin: bool, refout: bool, xorout: $t) -> CrcTable<$t> {
+                CrcTable::<$t>::new_const_with_width($default_width, poly, init, refin, refout, xorout)
+            }
+
+            /// A `const fn` version of [`new_with_width`](#method.new_with_width).
+            pub const fn new_const_with_width(width: usize, poly: $t, init: $t, refin: bool, refout: bool, xorout: $t) -> CrcTable<$t> {
+                let params = CrcParams { width, poly, init, refin, refout, xorout };
+                CrcTable::<$t>::validate_const(&params);
+                CrcTable::<$t>::from_parts_const(params, $table(poly, width, refin))
+            }
+
+            /// A `const fn` constructor from the parameters and a lookup table computed
+            /// in advance, e. g. by `codegen::generate_rust` (with the `codegen` feature)
+            /// in a build script. Unlike [`new_const`](#method.new_const), nothing
+            /// is computed at compile time besides validating the parameters.
+            /// Unlike [`from_parts`](#method.from_parts), the table is embedded in the spec,
+            /// and it isn't checked.
+            ///
+            /// ```
+            /// use crc_rocksoft::primitive::*;
+            ///
+            #[doc = concat!("const TABLE: Table<", stringify!($t), "> = const_table_", stringify!($t), "(0x07, 8, false);")]
+            #[doc = concat!("static SPEC: CrcTable<", stringify!($t), "> = CrcTable::<", stringify!($t), ">::from_parts_const(")]
+            ///     CrcParams { width: 8, poly: 0x07, init: 0, refin: false, refout: false, xorout: 0 },
+            ///     TABLE,
+            /// );
+            /// assert_eq!(SPEC.check(), 0xF4);
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// Panics if the parameters are invalid, which is a compile-time error
+            /// in a constant context; see [`try_new`](#method.try_new).
+            /// The table must have been computed for the same `poly`, width and `refin`;
+            /// this is not verified.
+            pub const fn from_parts_const(params: CrcParams<$t>, table: Table<$t>) -> CrcTable<$t> {
+                const BITS: usize = <$t>::BITS as usize;
+                CrcTable::<$t>::validate_const(&params);
+                let CrcParams { width, poly, init, refin, refout, xorout } = params;
+                CrcTable {
+                    width,
+                    poly,
+                    init,
+                    refin,
+                    refout,
+                    xorout,
+                    start: if refin { init.reverse_bits() >> (BITS - width) } else { init },
+                    table
+                }
+            }
+
+            const fn validate_const(params: &CrcParams<$t>) {
+                const BITS: usize = <$t>::BITS as usize;
+                assert!(params.width > 0 && params.width <= BITS, "unsupported width");
+                assert!(params.poly != 0, "the poly must not be zero");
+                let excess = !(<$t>::MAX >> (BITS - params.width));
+                assert!(params.poly & excess == 0, "the value of poly doesn't fit in the width of the algorithm");
+                assert!(params.init & excess == 0, "the value of init doesn't fit in the width of the algorithm");
+                assert!(params.xorout & excess == 0, "the value of xorout doesn't fit in the width of the algorithm");
+            }
+        }
+    };
+}
+impl_new_const!(u8, const_table_u8, 8);
+impl_new_const!(u16, const_table_u16, 16);
+impl_new_const!(u32, const_table_u32, 32);
+impl_new_const!(u64, const_table_u64, 64);
+impl_new_const!(u128, const_table_u128, 128);
+impl_new_const!(usize, const_table_usize, 32);
+
+/// Computes the checksum of `data` in one go, without a hasher.
+///
+/// ```
+/// use crc_rocksoft::catalog;
+/// use crc_rocksoft::primitive::checksum;
+///
+/// assert_eq!(checksum(&catalog::crc32_iso_hdlc(), b"123456789"), 0xCBF43926);
+/// ```
+pub fn checksum<T: ValueType>(spec: &CrcTable<T>, data: &[u8]) -> T {
+    spec.finish(spec.checksum_with_state(spec.start(), data))
+}
+
+pub(super) fn start_value<T: ValueType>(init: T, width: usize, refin: bool) -> T {
+    if refin { reflect(init, width) } else { init }
+}
+
+/// The implementation of [`CrcTable::update_bits`](struct.CrcTable.html#method.update_bits)
+/// for any spec whose register is kept reflected if `refin` is set.
+pub(super) fn update_bits<T: ValueType>(spec: &impl CrcSpec<T>, value: T, bits: u8, nbits: usize) -> T {
+    assert!(nbits <= 8, "can't process more than 8 bits at once: {}", nbits);
+    let zero = T::from(0);
+    let one = T::from(1);
+    let mut value = value;
+    if spec.refin() {
+        let poly = reflect(spec.poly(), spec.width());
+        for i in 0..nbits {
+            let feedback = ((value & one) != zero) != ((bits >> i) & 1 == 1);
+            value = value >> 1;
+            if feedback {
+                value = value ^ poly;
+            }
+        }
+    } else {
+        let top_bit_mask = one << ((spec.width() - 1) as u8);
+        let mask = T::mask(spec.width());
+        for i in (0..nbits).rev() {
+            let feedback = ((value & top_bit_mask) != zero) != ((bits >> i) & 1 == 1);
+            value = (value << 1) & mask;
+            if feedback {
+                value = value ^ spec.poly();
+            }
+        }
+    }
+    value
+}
+
+/// The implementation of [`CrcTable::update_last_bits`](struct.CrcTable.html#method.update_last_bits)
+/// for any spec whose register is kept reflected if `refin` is set.
+pub(super) fn update_last_bits<T: ValueType>(spec: &impl CrcSpec<T>, value: T, byte: u8, nbits: u8) -> T {
+    assert!(nbits <= 8, "can't process more than 8 bits at once: {}", nbits);
+    let bits = if spec.refin() || nbits == 0 { byte } else { byte >> (8 - nbits) };
+    update_bits(spec, value, bits, nbits as usize)
+}
+
+pub(super) fn validate<T: ValueType>(width: usize, poly: T, init: T, xorout: T) -> Result<(), CrcError<T>> {
+    if width == 0 || width > T::BITS as usize {
+        return Err(CrcError::UnsupportedWidth(width));
+    }
+    let zero = T::from(0);
+    if poly == zero {
+        return Err(CrcError::ZeroPoly);
+    }
+    let excess = !T::mask(width);
+    for &(field, value) in &[("poly", poly), ("init", init), ("xorout", xorout)] {
+        if value & excess != zero {
+            return Err(CrcError::ValueExceedsWidth { field, value });
+        }
+    }
+    Ok(())
+}
+
+/// The largest bit size of a `ValueType` whose shifts can be expressed with a `u8`.
+const MAX_BITS: usize = 256;
+
+fn gf2_matrix_times<T: ValueType>(matrix: &[T], vector: T) -> T {
+    let zero = T::from(0);
+    let one = T::from(1);
+    let mut result = zero;
+    for (i, &column) in matrix.iter().enumerate() {
+        if (vector >> (i as u8)) & one != zero {
+            result = result ^ column;
+        }
+    }
+    result
+}
+
+fn gf2_matrix_square<T: ValueType>(square: &mut [T], matrix: &[T]) {
+    for (result, &column) in square.iter_mut().zip(matrix.iter()) {
+        *result = gf2_matrix_times(matrix, column);
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     macro_rules! common_tests_for {
@@ -69,7 +841,7 @@ mod tests {
 
                 #[test]
                 fn width() {
-                    assert_eq!(CrcTable::new(0 as $t, 0 as $t, false, false, 0 as $t).width(), $w);
+                    assert_eq!(CrcTable::new(1 as $t, 0 as $t, false, false, 0 as $t).width(), $w);
                 }
             }
         }
@@ -79,4 +851,1020 @@ mod tests {
     common_tests_for!(u16, test_u16, 16);
     common_tests_for!(u32, test_u32, 32);
     common_tests_for!(u64, test_u64, 64);
+    common_tests_for!(u128, test_u128, 128);
+    common_tests_for!(usize, test_usize, 32);
+
+    #[test]
+    fn table() {
+        use super::super::{CrcTable, Table, fill_table};
+        let spec = CrcTable::new(0x8005u16, 0u16, true, true, 0u16);
+        let mut expected: Table<u16> = [0; 256];
+        fill_table(&mut expected, 0x8005, 16, true);
+        assert_eq!(&spec.table()[..], &expected[..]);
+    }
+
+    /// The well-known entries of zlib's CRC-32 table, and a dump of the first row,
+    /// as one would do when comparing with another library's table.
+    #[test]
+    fn table_known_entries() {
+        use super::super::CrcTable;
+        use std::string::String;
+        use core::fmt::Write;
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        let table = spec.table();
+        assert_eq!((table[0], table[1], table[128], table[255]), (0x00000000, 0x77073096, 0xEDB88320, 0x2D02EF8D));
+        let mut dump = String::new();
+        for entry in &table[..8] {
+            write!(dump, "0x{:08x}, ", entry).unwrap();
+        }
+        assert_eq!(dump, "0x00000000, 0x77073096, 0xee0e612c, 0x990951ba, 0x076dc419, 0x706af48f, 0xe963a535, 0x9e6495a3, ");
+    }
+
+    #[test]
+    fn register_stays_within_width() {
+        use super::CrcTable;
+        for &refin in &[false, true] {
+            let spec = CrcTable::new_with_width(10, 0x233u16, 0x3FFu16, refin, false, 0u16);
+            let mut value = spec.start();
+            for b in 0..=255 {
+                value = spec.update(value, b);
+                assert_eq!(value & !0x3FF, 0, "refin: {}, byte: {}", refin, b);
+            }
+        }
+    }
+
+    #[test]
+    fn sub_byte_widths() {
+        use super::CrcTable;
+        use primitive::reference::reference_crc;
+        use std::vec::Vec;
+        // CRC-5/USB and CRC-7/MMC
+        assert_eq!(CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8).check(), 0x19);
+        assert_eq!(CrcTable::new_with_width(7, 0x09u8, 0u8, false, false, 0u8).check(), 0x75);
+        let data: Vec<u8> = (0..=255).collect();
+        for width in 3..8 {
+            for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+                let spec = CrcTable::new_with_width(width, 0x03u8, 0x05u8, refin, refout, 0x01u8);
+                let value = data.iter().fold(spec.start(), |value, &b| spec.update(value, b));
+                assert_eq!(spec.finish(value), reference_crc(&spec, &data), "{:?}", spec);
+            }
+        }
+    }
+
+    #[test]
+    fn refout_reflects_width_bits() {
+        use super::CrcTable;
+        use primitive::reference::reference_crc;
+        // CRC-24/FLEXRAY-A, with every combination of refin and refout
+        for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+            let spec = CrcTable::new_with_width(24, 0x5D6DCBu32, 0xFEDCBAu32, refin, refout, 0u32);
+            let value = b"123456789".iter().fold(spec.start(), |value, &b| spec.update(value, b));
+            let crc = spec.finish(value);
+            assert_eq!(crc & !0xFFFFFF, 0, "refin: {}, refout: {}", refin, refout);
+            assert_eq!(crc, reference_crc(&spec, b"123456789"), "refin: {}, refout: {}", refin, refout);
+        }
+        let flexray_a = CrcTable::new_with_width(24, 0x5D6DCBu32, 0xFEDCBAu32, false, false, 0u32);
+        assert_eq!(flexray_a.check(), 0x7979BD);
+        let reflected = CrcTable::new_with_width(24, 0x5D6DCBu32, 0xFEDCBAu32, false, true, 0u32);
+        assert_eq!(reflected.check(), 0xBD9E9E);
+    }
+
+    #[test]
+    fn finish_masks_to_width() {
+        use super::CrcTable;
+        // CRC-12/UMTS: refin != refout, so the high bits would be reflected into the checksum
+        let spec = CrcTable::new_with_width(12, 0x80Fu16, 0u16, false, true, 0u16);
+        let value = b"123456789".iter().fold(spec.start(), |value, &b| spec.update(value, b));
+        assert_eq!(spec.finish(value), 0xDAF);
+        assert_eq!(spec.finish(value | 0xF000), 0xDAF);
+        let xored = CrcTable::new_with_width(12, 0x80Fu16, 0u16, false, false, 0xFFFu16);
+        assert_eq!(xored.finish(0xF123), 0xEDC);
+    }
+
+    #[test]
+    fn new_koopman() {
+        use super::CrcTable;
+        let spec = CrcTable::new_koopman(0x82608EDBu32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_eq!(spec.poly, 0x04C11DB7);
+        assert_eq!(spec.check(), 0xCBF43926);
+        assert_eq!(CrcTable::new_koopman(0xC002u16, 0xFFFFu16, true, true, 0u16).check(), 0x4B37);
+    }
+
+    mod test_reversed {
+        use std::vec::Vec;
+        use super::super::CrcTable;
+
+        #[test]
+        fn new_reversed() {
+            let canonical = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            let reversed = CrcTable::new_reversed(0xEDB88320u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert_eq!(reversed.poly, 0x04C11DB7);
+            assert_eq!(reversed.reversed_poly(), 0xEDB88320);
+            let data: Vec<u8> = (0..=255).collect();
+            for len in 0..data.len() {
+                let crc = |spec: &CrcTable<u32>| spec.finish(data[..len].iter().fold(spec.start(), |v, &b| spec.update(v, b)));
+                assert_eq!(crc(&reversed), crc(&canonical));
+            }
+        }
+
+        #[test]
+        fn reciprocal() {
+            let spec = CrcTable::new(0x8005u16, 0u16, true, true, 0u16);
+            let reciprocal = spec.reciprocal();
+            assert_eq!(reciprocal.poly, 0x4003);
+            assert_eq!((reciprocal.width, reciprocal.init, reciprocal.refin, reciprocal.refout, reciprocal.xorout), (16, 0, true, true, 0));
+            assert_eq!(reciprocal.reciprocal().poly, 0x8005);
+        }
+
+        #[test]
+        fn reflected_poly() {
+            use ::CrcSpec;
+            let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert_eq!(CrcSpec::reflected_poly(&crc32), 0xEDB88320);
+            let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            assert_eq!(CrcSpec::reflected_poly(&crc24), 0xDF3261);
+            #[cfg(feature = "catalog-crc64")]
+            {
+                let entry = ::catalog::from_name("CRC-64/XZ").unwrap();
+                assert_eq!(entry.reflected_poly(), 0xC96C5795D7870F42);
+            }
+        }
+
+        #[test]
+        fn sub_byte_width() {
+            let spec = CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8);
+            assert_eq!(spec.reversed_poly(), 0x14);
+            assert_eq!(spec.reciprocal().poly, 0x09);
+        }
+    }
+
+    mod test_from_const_table {
+        use super::super::{CrcTable, Table};
+        use primitive::const_table_u32;
+
+        static CRC32: Table<u32> = const_table_u32(0x04C11DB7, 32, true);
+        static CRC24: Table<u32> = const_table_u32(0x864CFB, 24, false);
+
+        #[test]
+        fn crc32() {
+            let spec = CrcTable::from_const_table(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF, &CRC32);
+            assert_eq!(spec.table()[..], CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF).table()[..]);
+            assert_eq!(spec.check(), 0xCBF43926);
+        }
+
+        #[test]
+        fn crc24() {
+            let spec = CrcTable::from_const_table_with_width(24, 0x864CFBu32, 0xB704CE, false, false, 0, &CRC24);
+            assert_eq!(spec.check(), 0x21CF02);
+        }
+
+        #[test]
+        #[should_panic(expected = "the poly must not be zero")]
+        fn invalid_params() {
+            CrcTable::from_const_table(0u32, 0, true, true, 0, &CRC32);
+        }
+
+        #[test]
+        #[cfg(debug_assertions)]
+        #[should_panic(expected = "the table doesn't match the parameters")]
+        fn mismatched_table() {
+            let table = ::primitive::const_table_u16(0x8005, 16, false);
+            CrcTable::from_const_table(0x8005u16, 0, true, true, 0, &table);
+        }
+    }
+
+    mod test_new_const {
+        use super::super::{CrcTable, CrcParams, const_table_u32};
+
+        static CRC32: CrcTable<u32> = CrcTable::<u32>::new_const(0x04C11DB7, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+        static CRC24: CrcTable<u32> = CrcTable::<u32>::new_const_with_width(24, 0x864CFB, 0xB704CE, false, false, 0);
+        static CRC5: CrcTable<u8> = CrcTable::<u8>::new_const_with_width(5, 0x05, 0x1E, true, true, 0x1F);
+        const CRC64: CrcTable<u64> = CrcTable::<u64>::new_const(0x42F0E1EBA9EA3693, !0, true, true, !0);
+        static CRC32_USIZE: CrcTable<usize> = CrcTable::<usize>::new_const(0x04C11DB7, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+
+        fn assert_identical<T: ::primitive::ValueType>(spec: &CrcTable<T>, runtime: CrcTable<T>) {
+            assert!(*spec == runtime);
+            assert_eq!(spec.start(), runtime.start());
+            assert_eq!(&spec.table()[..], &runtime.table()[..]);
+        }
+
+        #[test]
+        fn identical_to_runtime() {
+            assert_identical(&CRC32, CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF));
+            assert_identical(&CRC24, CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, false, false, 0));
+            assert_identical(&CRC5, CrcTable::new_with_width(5, 0x05u8, 0x1E, true, true, 0x1F));
+            assert_identical(&CRC64, CrcTable::new(0x42F0E1EBA9EA3693u64, !0, true, true, !0));
+            assert_identical(&CRC32_USIZE, CrcTable::new(0x04C11DB7usize, 0xFFFFFFFF, true, true, 0xFFFFFFFF));
+            let crc82 = CrcTable::<u128>::new_const_with_width(82, 0x0308C0111011401440411, 0, true, true, 0);
+            assert_identical(&crc82, CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0, true, true, 0));
+            assert_identical(&CrcTable::<u16>::new_const(0x1021, 0xFFFF, false, false, 0), CrcTable::new(0x1021u16, 0xFFFF, false, false, 0));
+        }
+
+        #[test]
+        fn check_values() {
+            assert_eq!(CRC32.check(), 0xCBF43926);
+            assert_eq!(CRC24.check(), 0x21CF02);
+            assert_eq!(CRC64.check(), 0x995DC9BBDF1939FA);
+        }
+
+        static CRC32_PARTS: CrcTable<u32> = CrcTable::<u32>::from_parts_const(
+            CrcParams { width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF },
+            const_table_u32(0x04C11DB7, 32, true)
+        );
+
+        #[test]
+        fn from_parts_const() {
+            assert_identical(&CRC32_PARTS, CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF));
+        }
+
+        #[test]
+        #[should_panic(expected = "the value of init doesn't fit in the width of the algorithm")]
+        fn invalid_at_run_time() {
+            CrcTable::<u16>::new_const_with_width(12, 0x80F, 0x1000, false, false, 0);
+        }
+    }
+
+    mod test_from_parts {
+        use ::CrcHasher;
+        use super::super::{CrcTable, CrcTableHasher, CrcParams, Table, TableMismatch, const_table_u32, const_table_u16};
+
+        static CRC32: Table<u32> = const_table_u32(0x04C11DB7, 32, true);
+        static CRC16: Table<u16> = const_table_u16(0x1021, 16, false);
+        static CORRUPTED: Table<u32> = {
+            let mut table = const_table_u32(0x04C11DB7, 32, true);
+            table[0x9C] ^= 0x100;
+            table
+        };
+
+        fn crc32_params() -> CrcParams<u32> {
+            CrcParams { width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true, xorout: 0xFFFFFFFF }
+        }
+
+        #[test]
+        fn borrows_static_table() {
+            let spec = CrcTable::from_parts(crc32_params(), &CRC32).unwrap();
+            assert!(::core::ptr::eq(spec.table(), &CRC32));
+            assert_eq!(spec.check(), 0xCBF43926);
+            assert_eq!(spec.params(), crc32_params());
+            let owned = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+            assert_eq!(spec.start(), owned.start());
+            assert_eq!(spec.residue(), owned.residue());
+            assert_eq!(format!("{}", spec), format!("{}", owned));
+
+            let mut hasher = CrcTableHasher::from(&spec);
+            hasher.update_from_slice(&[0x5A; 1000]);
+            let mut expected = CrcTableHasher::from(&owned);
+            expected.update_from_slice(&[0x5A; 1000]);
+            assert_eq!(hasher.finish(), expected.finish());
+
+            let xmodem = CrcParams { width: 16, poly: 0x1021u16, init: 0, refin: false, refout: false, xorout: 0 };
+            assert_eq!(CrcTable::from_parts(xmodem, &CRC16).unwrap().check(), 0x31C3);
+        }
+
+        #[test]
+        fn mismatch() {
+            assert_eq!(CrcTable::from_parts(crc32_params(), &CORRUPTED).err(), Some(TableMismatch { index: 0x9C }));
+            assert_eq!(
+                CrcTable::from_parts(CrcParams { refin: false, ..crc32_params() }, &CRC32).err(),
+                Some(TableMismatch { index: 0x01 })
+            );
+            assert_eq!(
+                CrcTable::from_parts(CrcParams { poly: 0x1EDC6F41, ..crc32_params() }, &CRC32).err(),
+                Some(TableMismatch { index: 0x01 })
+            );
+            assert_eq!(format!("{}", TableMismatch { index: 0x9C }), "the entry for 0x9c doesn't match the parameters");
+        }
+
+        #[test]
+        #[should_panic(expected = "the poly must not be zero")]
+        fn invalid_params() {
+            let _ = CrcTable::from_parts(CrcParams { poly: 0, ..crc32_params() }, &CRC32);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    mod test_new_boxed {
+        use std::thread;
+        use ::CrcHasher;
+        use super::super::{CrcParams, CrcTable, CrcTableHasher};
+
+        #[test]
+        fn identical_to_new() {
+            for &(refin, refout) in &[(false, false), (false, true), (true, false), (true, true)] {
+                let boxed = CrcTable::new_boxed_with_width(24, 0x864CFBu32, 0xB704CE, refin, refout, 0x5A5A5A);
+                let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CE, refin, refout, 0x5A5A5A);
+                assert_eq!(CrcParams::from_spec(&boxed), CrcParams::from_spec(&spec));
+                assert_eq!(boxed.start(), spec.start());
+                assert_eq!(&boxed.table()[..], &spec.table()[..]);
+            }
+        }
+
+        #[test]
+        fn small_stack() {
+            // With their tables inline, these hashers alone would take 64 KiB, four times this stack.
+            let hashes = thread::Builder::new().stack_size(16 * 1024).spawn(|| {
+                let mut hashers: [_; 16] = ::core::array::from_fn(|i| CrcTableHasher::from(
+                    CrcTable::new_boxed_with_width(82, 0x0308C0111011401440411u128, i as u128, true, true, 0)
+                ));
+                for hasher in &mut hashers {
+                    hasher.update_from_slice(b"123456789");
+                }
+                hashers.map(|hasher| hasher.finish())
+            }).unwrap().join().unwrap();
+            assert_eq!(hashes[0], 0x09EA83F625023801FD612);
+            for (i, &hash) in hashes.iter().enumerate() {
+                let spec = CrcTable::new_with_width(82, 0x0308C0111011401440411u128, i as u128, true, true, 0);
+                assert_eq!(hash, spec.check());
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "the poly must not be zero")]
+        fn invalid_params() {
+            CrcTable::new_boxed(0u32, 0, false, false, 0);
+        }
+    }
+
+    mod test_with {
+        use super::super::CrcTable;
+
+        fn crc32() -> CrcTable<u32> {
+            CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32)
+        }
+
+        /// Marks the table, so that it can be told whether it was recomputed.
+        fn marked() -> CrcTable<u32> {
+            let mut spec = crc32();
+            spec.table[0] = 0xDEADBEEF;
+            spec
+        }
+
+        #[test]
+        fn variants() {
+            // CRC-32/JAMCRC, CRC-32/BZIP2, CRC-32/MPEG-2 and CRC-32/CKSUM
+            assert_eq!(crc32().with_xorout(0).check(), 0x340BC6D9);
+            assert_eq!(crc32().with_refin(false).with_refout(false).check(), 0xFC891918);
+            assert_eq!(crc32().with_refin(false).with_refout(false).with_xorout(0).check(), 0x0376E6E7);
+            assert_eq!(crc32().with_refin(false).with_refout(false).with_init(0).check(), 0x765E7680);
+            let spec = crc32().with_refin(false).with_refin(true);
+            assert!(spec == crc32());
+            assert_eq!(&spec.table()[..], &crc32().table()[..]);
+        }
+
+        #[test]
+        fn start() {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            assert_eq!(spec.start(), 0xB704CE);
+            let spec = spec.with_refin(true);
+            assert_eq!(spec.start(), 0x7320ED);
+            let spec = spec.with_init(0x000001);
+            assert_eq!(spec.start(), 0x800000);
+            assert_eq!(spec.with_refin(false).start(), 0x000001);
+        }
+
+        #[test]
+        fn table_reused() {
+            assert_eq!(marked().with_init(0).table[0], 0xDEADBEEF);
+            assert_eq!(marked().with_xorout(0).table[0], 0xDEADBEEF);
+            assert_eq!(marked().with_refout(false).table[0], 0xDEADBEEF);
+            assert_eq!(marked().with_refin(true).table[0], 0xDEADBEEF);
+            assert_eq!(marked().with_refin(false).table[0], 0);
+        }
+
+        #[test]
+        #[should_panic(expected = "doesn't fit in the width")]
+        fn value_exceeds_width() {
+            CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32).with_xorout(0xFFFFFFFF);
+        }
+
+        #[test]
+        fn sub_width() {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            let direct = CrcTable::new_with_width(24, 0x864CFBu32, 0xFFFFFFu32, true, true, 0u32);
+            let derived = spec.with_init(0xFFFFFF).with_refin(true).with_refout(true);
+            assert!(derived == direct);
+            assert_eq!(&derived.table()[..], &direct.table()[..]);
+            assert_eq!(derived.check(), direct.check());
+        }
+    }
+
+    #[test]
+    fn clone() {
+        use super::CrcTable;
+        use std::thread;
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        let threads: std::vec::Vec<_> = (0..4).map(|_| {
+            let spec = spec.clone();
+            thread::spawn(move || spec.check())
+        }).collect();
+        for t in threads {
+            assert_eq!(t.join().unwrap(), 0xCBF43926);
+        }
+        let cloned = spec.clone();
+        assert_eq!(cloned.table()[..], spec.table()[..]);
+    }
+
+    mod test_finish_bytes {
+        use super::super::CrcTable;
+
+        fn register<T: ::primitive::ValueType>(spec: &CrcTable<T>) -> T {
+            b"123456789".iter().fold(spec.start(), |value, &b| spec.update(value, b))
+        }
+
+        #[test]
+        fn crc32() {
+            let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert_eq!(&*spec.finish_be_bytes(register(&spec)), &[0xCB, 0xF4, 0x39, 0x26]);
+            assert_eq!(&*spec.finish_le_bytes(register(&spec)), &[0x26, 0x39, 0xF4, 0xCB]);
+        }
+
+        #[test]
+        fn crc24_is_three_bytes() {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            assert_eq!(&*spec.finish_be_bytes(register(&spec)), &[0x21, 0xCF, 0x02]);
+            assert_eq!(&*spec.finish_le_bytes(register(&spec)), &[0x02, 0xCF, 0x21]);
+        }
+
+        #[test]
+        fn sub_byte() {
+            let spec = CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8);
+            assert_eq!(&*spec.finish_be_bytes(register(&spec)), &[0x19]);
+            assert_eq!(&*spec.finish_le_bytes(register(&spec)), &[0x19]);
+        }
+
+        #[test]
+        fn crc82_wide() {
+            use primitive::Wide;
+            let mut poly = [0u8; 11];
+            poly[2..].copy_from_slice(&[0x30, 0x8C, 0x01, 0x11, 0x01, 0x14, 0x01, 0x44, 0x11]);
+            let spec = CrcTable::new_with_width(82, Wide(poly), Wide([0; 11]), true, true, Wide([0; 11]));
+            let bytes = spec.finish_be_bytes(register(&spec));
+            assert_eq!(bytes.len(), 11);
+            assert_eq!(&*bytes, &spec.check().0[..]);
+        }
+    }
+
+    mod test_trailer {
+        use super::super::CrcTable;
+        use interop::ByteOrder;
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn append_checksum() {
+            use std::vec::Vec;
+            let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            let mut frame: Vec<u8> = b"123456789".to_vec();
+            crc24.append_checksum(&mut frame, ByteOrder::BigEndian);
+            assert_eq!(&frame[9..], &[0x21, 0xCF, 0x02]);
+            let mut frame: Vec<u8> = b"123456789".to_vec();
+            crc24.append_checksum(&mut frame, ByteOrder::LittleEndian);
+            assert_eq!(&frame[9..], &[0x02, 0xCF, 0x21]);
+            let mut empty = Vec::new();
+            crc24.append_checksum(&mut empty, ByteOrder::BigEndian);
+            assert_eq!(crc24.strip_and_verify(&empty, ByteOrder::BigEndian), Some(&[][..]));
+        }
+
+        #[test]
+        fn strip_and_verify() {
+            let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            let frame = b"123456789\xCB\xF4\x39\x26";
+            assert_eq!(crc32.strip_and_verify(frame, ByteOrder::BigEndian), Some(&b"123456789"[..]));
+            assert_eq!(crc32.strip_and_verify(frame, ByteOrder::LittleEndian), None);
+            assert_eq!(crc32.strip_and_verify(&frame[1..], ByteOrder::BigEndian), None);
+            assert_eq!(crc32.strip_and_verify(&frame[..12], ByteOrder::BigEndian), None);
+        }
+
+        #[test]
+        fn strip_and_verify_too_short() {
+            let crc16 = CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16);
+            assert_eq!(crc16.strip_and_verify(&[], ByteOrder::BigEndian), None);
+            assert_eq!(crc16.strip_and_verify(&[0xFF], ByteOrder::BigEndian), None);
+            assert_eq!(crc16.strip_and_verify(&[0xFF, 0xFF], ByteOrder::BigEndian), Some(&[][..]));
+        }
+    }
+
+    mod test_update_bits {
+        use std::vec::Vec;
+        use ::{CrcSpec, CrcHasher, CrcBitInput};
+        use super::super::{CrcTable, CrcTableHasher, reflect};
+        use primitive::reference::reference_update_bit;
+
+        /// The bits of `data`, in the order in which the algorithm processes them.
+        fn message_bits(spec: &CrcTable<u32>, data: &[u8], nbits: usize) -> Vec<bool> {
+            (0..nbits).map(|i| {
+                let bit = if spec.refin() { i % 8 } else { 7 - i % 8 };
+                (data[i / 8] >> bit) & 1 == 1
+            }).collect()
+        }
+
+        fn reference(spec: &CrcTable<u32>, bits: &[bool]) -> u32 {
+            let register = bits.iter().fold(spec.init(), |register, &bit| reference_update_bit(spec, register, bit));
+            (if spec.refout() { reflect(register, spec.width()) } else { register }) ^ spec.xorout()
+        }
+
+        fn specs() -> Vec<CrcTable<u32>> {
+            let mut specs = Vec::new();
+            for &(refin, refout) in &[(false, false), (true, true), (true, false), (false, true)] {
+                specs.push(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFF, refin, refout, 0xFFFFFFFF));
+                specs.push(CrcTable::new_with_width(5, 0x05u32, 0x1F, refin, refout, 0x1F));
+                specs.push(CrcTable::new_with_width(13, 0x1CF5u32, 0, refin, refout, 0));
+            }
+            specs
+        }
+
+        #[test]
+        fn against_reference() {
+            let data = [0xA7u8, 0x3C];
+            for spec in &specs() {
+                for &nbits in &[0, 1, 5, 8, 13, 16] {
+                    let bits = message_bits(spec, &data, nbits);
+                    let mut hasher = CrcTableHasher::from(spec);
+                    for &bit in &bits {
+                        hasher.update_bit(bit);
+                    }
+                    assert_eq!(hasher.finish(), reference(spec, &bits), "{:?}, {} bits", spec, nbits);
+
+                    let (full_bytes, tail_bits) = (nbits / 8, nbits % 8);
+                    let mut value = spec.start();
+                    for &b in &data[..full_bytes] {
+                        value = spec.update(value, b);
+                    }
+                    // The bits to process are the first ones of the next byte, moved to the low end.
+                    let tail = match data.get(full_bytes) {
+                        Some(&b) if tail_bits > 0 && !spec.refin() => b >> (8 - tail_bits),
+                        Some(&b) => b,
+                        None => 0,
+                    };
+                    let value = spec.update_bits(value, tail, tail_bits);
+                    assert_eq!(spec.finish(value), reference(spec, &bits), "{:?}, {} bits at once", spec, nbits);
+                }
+            }
+        }
+
+        #[test]
+        fn last_bits() {
+            let data = [0xA7u8, 0x3C];
+            for spec in &specs() {
+                let bits = message_bits(spec, &data, 11);
+                let mut hasher = CrcTableHasher::from(spec);
+                hasher.update(data[0]);
+                hasher.update_last_bits(data[1], 3);
+                assert_eq!(hasher.finish(), reference(spec, &bits), "{:?}", spec);
+
+                let value = spec.update(spec.start(), data[0]);
+                assert_eq!(spec.update_last_bits(value, data[1], 0), value);
+                assert_eq!(spec.update_last_bits(value, data[1], 8), spec.update(value, data[1]));
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "can't process more than 8 bits at once")]
+        fn too_many_last_bits() {
+            CrcTable::new(0x07u8, 0u8, false, false, 0u8).update_last_bits(0, 0, 9);
+        }
+
+        #[test]
+        fn whole_byte() {
+            for spec in &specs() {
+                for byte in 0..=255u8 {
+                    let value = spec.start();
+                    assert_eq!(spec.update_bits(value, byte, 8), spec.update(value, byte));
+                }
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "can't process more than 8 bits at once")]
+        fn too_many_bits() {
+            CrcTable::new(0x07u8, 0u8, false, false, 0u8).update_bits(0, 0, 9);
+        }
+
+        #[test]
+        fn wide_types() {
+            let spec = CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128);
+            let mut hasher = CrcTableHasher::from(&spec);
+            for i in 0..8 {
+                hasher.update_bit((b'1' >> i) & 1 == 1);
+            }
+            hasher.update_from_slice(b"23456789");
+            assert_eq!(hasher.finish(), spec.check());
+        }
+    }
+
+    mod test_check {
+        use super::super::CrcTable;
+
+        #[test]
+        fn crc32_iso_hdlc() {
+            assert_eq!(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).check(), 0xCBF43926);
+        }
+
+        #[test]
+        fn crc16_ccitt_false() {
+            assert_eq!(CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16).check(), 0x29B1);
+        }
+
+        #[test]
+        fn crc5_usb() {
+            assert_eq!(CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8).check(), 0x19);
+        }
+
+        #[test]
+        fn repeatable() {
+            let spec = CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16);
+            assert_eq!(spec.check(), 0x4B37);
+            assert_eq!(spec.check(), 0x4B37);
+        }
+    }
+
+    mod test_verify {
+        use super::super::CrcTable;
+        use ::interop::{to_be_bytes, to_le_bytes};
+        use std::vec::Vec;
+
+        fn with_trailer(spec: &CrcTable<u32>, data: &[u8]) -> Vec<u8> {
+            let crc = spec.check();
+            let mut message = data.to_vec();
+            if spec.refout {
+                message.extend(to_le_bytes(crc, spec.width));
+            } else {
+                message.extend(to_be_bytes(crc, spec.width));
+            }
+            message
+        }
+
+        #[test]
+        fn verify() {
+            let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert!(spec.verify(b"123456789", 0xCBF43926));
+            assert!(!spec.verify(b"123456789", 0xCBF43927));
+            assert!(!spec.verify(b"12345678", 0xCBF43926));
+        }
+
+        #[test]
+        fn verify_residue() {
+            let specs = [
+                CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32),
+                CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32),
+                CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32),
+                CrcTable::new_with_width(24, 0x00065Bu32, 0x555555u32, true, true, 0u32),
+            ];
+            for spec in &specs {
+                let mut message = with_trailer(spec, b"123456789");
+                assert!(spec.verify_residue(&message), "{:?}", spec);
+                message[3] ^= 0x10;
+                assert!(!spec.verify_residue(&message), "{:?}", spec);
+            }
+        }
+    }
+
+    mod test_residue {
+        use super::super::CrcTable;
+
+        #[test]
+        fn crc32_iso_hdlc() {
+            assert_eq!(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32).residue(), 0xDEBB20E3);
+        }
+
+        #[test]
+        fn crc32_bzip2() {
+            assert_eq!(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32).residue(), 0xC704DD7B);
+        }
+
+        #[test]
+        fn crc16_modbus() {
+            assert_eq!(CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16).residue(), 0x0000);
+        }
+
+        #[test]
+        fn crc16_genibus() {
+            assert_eq!(CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0xFFFFu16).residue(), 0x1D0F);
+        }
+
+        #[test]
+        fn crc15_mpt1327() {
+            assert_eq!(CrcTable::new_with_width(15, 0x6815u16, 0u16, false, false, 1u16).residue(), 0x6815);
+        }
+
+        #[test]
+        fn message_with_checksum() {
+            let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            let mut value = spec.start();
+            for &b in b"123456789" {
+                value = spec.update(value, b);
+            }
+            // Reflected checksums are transmitted starting from the least significant byte
+            for &b in &spec.finish(value).to_le_bytes() {
+                value = spec.update(value, b);
+            }
+            assert_eq!(spec.finish(value) ^ spec.xorout, spec.residue());
+        }
+    }
+
+    mod test_combine {
+        use std::vec::Vec;
+        use super::super::CrcTable;
+        use ::primitive::ValueType;
+
+        fn crc<T: ValueType>(spec: &CrcTable<T>, data: &[u8]) -> T {
+            spec.finish(data.iter().fold(spec.start(), |value, &b| spec.update(value, b)))
+        }
+
+        fn check_all_splits<T: ValueType>(spec: CrcTable<T>) {
+            let data: Vec<u8> = (0..300u32).map(|i| (i * 167 + 13) as u8).collect();
+            for &len in &[0, 1, 9, 64, 300] {
+                let data = &data[..len];
+                let whole = crc(&spec, data);
+                for split in 0..=len {
+                    let (a, b) = data.split_at(split);
+                    assert_eq!(
+                        spec.combine(crc(&spec, a), crc(&spec, b), b.len()), whole,
+                        "len {}, split at {}", len, split
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn crc32_iso_hdlc() {
+            check_all_splits(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+        }
+
+        #[test]
+        fn crc32_bzip2() {
+            check_all_splits(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32));
+        }
+
+        #[test]
+        fn crc16_modbus() {
+            check_all_splits(CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16));
+        }
+
+        #[test]
+        fn crc8_smbus() {
+            check_all_splits(CrcTable::new(0x07u8, 0u8, false, false, 0u8));
+        }
+
+        #[test]
+        fn crc5_usb() {
+            check_all_splits(CrcTable::new_with_width(5, 0x05u8, 0x1Fu8, true, true, 0x1Fu8));
+        }
+
+        #[test]
+        fn crc12_3gpp() {
+            check_all_splits(CrcTable::new_with_width(12, 0x80Fu16, 0u16, false, true, 0u16));
+        }
+
+        #[test]
+        fn crc32_refin_only() {
+            check_all_splits(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0xFFFFFFFFu32));
+        }
+
+        #[test]
+        fn crc82_darc() {
+            check_all_splits(CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128));
+        }
+
+        #[test]
+        fn crc64_xz() {
+            check_all_splits(CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64));
+        }
+    }
+
+    mod test_eq {
+        use super::super::CrcTable;
+        use std::collections::HashSet;
+
+        #[test]
+        fn eq() {
+            let a = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert!(a == a.clone());
+            assert!(a == CrcTable::new_reversed(0xEDB88320u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+            assert!(a != CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0u32));
+            assert!(a != CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, true, 0xFFFFFFFFu32));
+            assert!(a != CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0xFFFFFFFFu32));
+            assert!(a != CrcTable::new(0x04C11DB7u32, 0xFFFFFFFEu32, true, true, 0xFFFFFFFFu32));
+            assert!(a != CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+        }
+
+        #[test]
+        fn width_matters() {
+            let a = CrcTable::new_with_width(24, 0x864CFBu32, 0u32, false, false, 0u32);
+            let b = CrcTable::new_with_width(25, 0x864CFBu32, 0u32, false, false, 0u32);
+            assert!(a != b);
+        }
+
+        #[test]
+        fn hash_set() {
+            let mut set = HashSet::new();
+            assert!(set.insert(CrcTable::new(0x8005u16, 0u16, true, true, 0u16)));
+            assert!(set.insert(CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16)));
+            assert!(!set.insert(CrcTable::new(0x8005u16, 0u16, true, true, 0u16)));
+            assert_eq!(set.len(), 2);
+        }
+    }
+
+    mod test_try_new {
+        use super::super::CrcTable;
+        use super::super::super::CrcError;
+
+        #[test]
+        fn valid() {
+            assert!(CrcTable::try_new_with_width(12, 0x80Fu16, 0u16, false, true, 0u16).is_ok());
+        }
+
+        #[test]
+        fn unsupported_width() {
+            assert_eq!(
+                CrcTable::try_new_with_width(17, 0x1021u16, 0u16, false, false, 0u16).err(),
+                Some(CrcError::UnsupportedWidth(17))
+            );
+            assert_eq!(
+                CrcTable::try_new_with_width(0, 0x1021u16, 0u16, false, false, 0u16).err(),
+                Some(CrcError::UnsupportedWidth(0))
+            );
+        }
+
+        #[test]
+        fn zero_poly() {
+            assert_eq!(CrcTable::try_new(0u32, 0u32, true, true, 0u32).err(), Some(CrcError::ZeroPoly));
+        }
+
+        #[test]
+        fn value_exceeds_width() {
+            assert_eq!(
+                CrcTable::try_new_with_width(12, 0x180Fu16, 0u16, false, true, 0u16).err(),
+                Some(CrcError::ValueExceedsWidth { field: "poly", value: 0x180F })
+            );
+            assert_eq!(
+                CrcTable::try_new_with_width(12, 0x80Fu16, 0xFFFFu16, false, true, 0u16).err(),
+                Some(CrcError::ValueExceedsWidth { field: "init", value: 0xFFFF })
+            );
+            assert_eq!(
+                CrcTable::try_new_with_width(12, 0x80Fu16, 0u16, false, true, 0x1000u16).err(),
+                Some(CrcError::ValueExceedsWidth { field: "xorout", value: 0x1000 })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "the poly must not be zero")]
+        fn new_panics() {
+            CrcTable::new(0u8, 0u8, false, false, 0u8);
+        }
+
+        #[test]
+        #[should_panic(expected = "the value of poly (6159) doesn't fit in the width of the algorithm")]
+        fn new_with_width_panics_on_wide_poly() {
+            CrcTable::new_with_width(12, 0x180Fu16, 0u16, false, false, 0u16);
+        }
+    }
+
+    mod test_checksum_with_state {
+        use super::super::CrcTable;
+
+        #[test]
+        fn in_parts() {
+            let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            let whole = spec.checksum_with_state(spec.start(), b"123456789");
+            for split in 0..10 {
+                let (head, tail) = b"123456789".split_at(split);
+                assert_eq!(spec.checksum_with_state(spec.checksum_with_state(spec.start(), head), tail), whole);
+            }
+            assert_eq!(spec.finish(whole), 0x21CF02);
+        }
+
+        #[test]
+        fn returns_register() {
+            let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert_eq!(spec.checksum_with_state(spec.start(), b""), spec.start());
+            assert_eq!(spec.checksum_with_state(spec.start(), b"123456789"), !0xCBF43926);
+        }
+    }
+
+    mod test_checksum_words {
+        use std::vec::Vec;
+        use super::super::CrcTable;
+        use primitive::{ValueType, Wide};
+        use primitive::test_util::Random;
+
+        fn data() -> Vec<u8> {
+            Random(0x9E3779B97F4A7C15).data(600)
+        }
+
+        /// Every alignment of the start and the end of the data, and random splits.
+        fn assert_bytewise<T: ValueType>(spec: CrcTable<T>) {
+            let data = data();
+            let bytewise = |value: T, data: &[u8]| data.iter().fold(value, |value, &b| spec.update(value, b));
+            for start in 0..8 {
+                for len in 0..40 {
+                    let data = &data[start..start + len];
+                    assert_eq!(spec.checksum_with_state(spec.start(), data), bytewise(spec.start(), data), "{:?}", spec);
+                }
+            }
+            let mut value = spec.start();
+            let mut expected = spec.start();
+            let mut rest = &data[..];
+            while !rest.is_empty() {
+                let len = (rest[0] as usize % 37).min(rest.len());
+                let (chunk, tail) = rest.split_at(len.max(1));
+                value = spec.checksum_with_state(value, chunk);
+                expected = bytewise(expected, chunk);
+                assert_eq!(value, expected, "{:?}", spec);
+                rest = tail;
+            }
+        }
+
+        #[test]
+        fn reflected() {
+            assert_bytewise(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32));
+            assert_bytewise(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, false, 0xFFFFFFFFu32));
+            assert_bytewise(CrcTable::new(0x42F0E1EBA9EA3693u64, !0u64, true, true, !0u64));
+            assert_bytewise(CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128));
+            assert_bytewise(CrcTable::new_with_width(40, 0x0004820009u64, 0u64, true, true, !0u64 >> 24));
+        }
+
+        #[test]
+        fn narrow_in_wide_type() {
+            assert_bytewise(CrcTable::new_with_width(24, 0x00065Bu32, 0x555555u32, true, true, 0u32));
+            assert_bytewise(CrcTable::new_with_width(16, 0x8005u32, 0xFFFFu32, true, true, 0u32));
+            assert_bytewise(CrcTable::new_with_width(5, 0x05u32, 0x1Fu32, true, true, 0x1Fu32));
+            assert_bytewise(CrcTable::new_with_width(5, 0x05u64, 0x1Fu64, true, true, 0x1Fu64));
+        }
+
+        #[test]
+        fn wide() {
+            let mut poly = [0u8; 8];
+            poly.copy_from_slice(&0x42F0E1EBA9EA3693u64.to_be_bytes());
+            assert_bytewise(CrcTable::new(Wide(poly), Wide([0xFF; 8]), true, true, Wide([0xFF; 8])));
+        }
+
+        /// Non-reflected algorithms, and types narrower than a word, aren't read by words.
+        #[test]
+        fn bytewise_only() {
+            assert_bytewise(CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, false, false, 0xFFFFFFFFu32));
+            assert_bytewise(CrcTable::new(0x8005u16, 0xFFFFu16, true, true, 0u16));
+        }
+    }
+
+    mod test_checksum {
+        use super::super::{CrcTable, checksum};
+        use ::CrcHasher;
+        use primitive::CrcTableHasher;
+
+        #[test]
+        fn check_values() {
+            let crc32 = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            assert_eq!(checksum(&crc32, b"123456789"), 0xCBF43926);
+            let crc24 = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+            assert_eq!(checksum(&crc24, b"123456789"), 0x21CF02);
+        }
+
+        #[test]
+        fn empty() {
+            let crc16 = CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0xFFFFu16);
+            assert_eq!(checksum(&crc16, b""), 0);
+        }
+
+        #[test]
+        fn matches_hasher() {
+            let spec = CrcTable::new(0x1EDC6F41u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+            let data = b"The quick brown fox jumps over the lazy dog";
+            let mut hasher = CrcTableHasher::from(&spec);
+            hasher.update_from_slice(data);
+            assert_eq!(checksum(&spec, data), hasher.finish());
+        }
+    }
+
+    mod test_usize_matches_u32 {
+        use super::super::CrcTable;
+
+        fn assert_same(narrow: CrcTable<u32>, wide: CrcTable<usize>) {
+            for ix in 0..256 {
+                assert_eq!(narrow.table[ix] as usize, wide.table[ix], "ix: {}", ix);
+            }
+            let (mut n, mut w) = (narrow.start(), wide.start());
+            for &b in b"123456789" {
+                n = narrow.update(n, b);
+                w = wide.update(w, b);
+                assert_eq!(n as usize, w);
+            }
+            assert_eq!(narrow.finish(n) as usize, wide.finish(w));
+        }
+
+        #[test]
+        fn reflected() {
+            assert_same(
+                CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32),
+                CrcTable::new(0x04C11DB7usize, 0xFFFFFFFFusize, true, true, 0xFFFFFFFFusize)
+            );
+        }
+
+        #[test]
+        fn non_reflected() {
+            assert_same(
+                CrcTable::new(0x04C11DB7u32, 0u32, false, false, 0xFFFFFFFFu32),
+                CrcTable::new(0x04C11DB7usize, 0usize, false, false, 0xFFFFFFFFusize)
+            );
+        }
+    }
 }
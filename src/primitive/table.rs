@@ -1,34 +1,109 @@
-use super::ValueType;
-use std::mem::size_of;
-use bit_reverse::ParallelReverse;
+use ::CrcSpec;
+use super::{ValueType, reflect};
+use super::spec::update_bits;
 
+/// A lookup table for byte-at-a-time CRC computation.
 pub type Table<T> = [T; 256];
 
-pub fn fill_table<T: ValueType>(table: &mut Table<T>, poly: T, reflected: bool) {
+/// Fills `table` with the lookup values for the given poly and width.
+///
+/// In a non-reflected table, the entry at index `ix` is the value of a zeroed register
+/// after processing the byte `ix`; for algorithms at least 8 bits wide, this is
+/// the remainder of dividing `ix` followed by `width` zero bits by the poly,
+/// and in particular, the entry at index 1 is the poly itself. A reflected table
+/// (for algorithms with `refin` set) is indexed by reflected bytes and contains
+/// reflected values: the entry at index `0x80` is the poly reflected within `width` bits.
+pub fn fill_table<T: ValueType>(table: &mut Table<T>, poly: T, width: usize, reflected: bool) {
     let zero = T::from(0);
-    let top_bit_mask = (!zero) ^ ((!zero) >> 1);
-    let control_byte_shift = ((size_of::<T>() - 1) * 8).to_u8();
+    let top_bit_mask = T::from(1) << ((width - 1) as u8);
+    let mask = T::mask(width);
     for ix in 0..256 {
-        let unreflected_byte = if reflected { (ix as u8).swap_bits() } else { ix as u8 };
-        let mut value: T = T::from(unreflected_byte) << control_byte_shift;
-        for _ in 0..8 {
-            value = if (value & top_bit_mask) != zero {
-                (value << 1) ^ poly
-            } else {
-                value << 1
-            };
+        let unreflected_byte = if reflected { (ix as u8).reverse_bits() } else { ix as u8 };
+        let mut value = zero;
+        for i in (0..8).rev() {
+            let feedback = ((value & top_bit_mask) != zero) != ((unreflected_byte >> i) & 1 == 1);
+            value = (value << 1) & mask;
+            if feedback {
+                value = value ^ poly;
+            }
         }
         if reflected {
-            value = value.swap_bits();
+            value = reflect(value, width);
         }
         table[ix as usize] = value;
     }
 }
 
+/// Checks a table computed in advance against the parameters of `spec`, returning the first
+/// byte whose entry is wrong. The entries for single bits are computed anew, and every other
+/// entry has to be the XOR of the entries for its bits, as it is in any CRC table,
+/// since the remainder is linear. This catches any corrupted entry in a fraction of the time
+/// that computing the table takes.
+pub(super) fn find_mismatch<T: ValueType>(spec: &impl CrcSpec<T>, table: &Table<T>) -> Option<u8> {
+    let zero = T::from(0);
+    for bit in 0..8 {
+        let index = 1u8 << bit;
+        if table[usize::from(index)] != update_bits(spec, zero, index, 8) {
+            return Some(index);
+        }
+    }
+    (0..256usize).find(|&index| {
+        let low_bit = index & index.wrapping_neg();
+        let expected = if index == 0 { zero } else { table[low_bit] ^ table[index ^ low_bit] };
+        table[index] != expected
+    }).map(|index| index as u8)
+}
+
+macro_rules! impl_const_table {
+    ($t:ty, $name:ident) => {
+        /// A `const fn` version of [`fill_table`](fn.fill_table.html) for `
+        #[doc = stringify!($t)]
+        /// `, for computing tables at compile time:
+        ///
+        /// ```
+        /// use crc_rocksoft::primitive::*;
+        ///
+        #[doc = concat!("static TABLE: Table<", stringify!($t), "> = ", stringify!($name), "(0x07, 8, true);")]
+        /// ```
+        pub const fn $name(poly: $t, width: usize, reflected: bool) -> Table<$t> {
+            const BITS: usize = <$t>::BITS as usize;
+            let top_bit_mask: $t = 1 << (width - 1);
+            let mask: $t = <$t>::MAX >> (BITS - width);
+            let mut table = [0; 256];
+            let mut ix = 0;
+            while ix < 256 {
+                let unreflected_byte = if reflected { (ix as u8).reverse_bits() } else { ix as u8 };
+                let mut value: $t = 0;
+                let mut i = 8;
+                while i > 0 {
+                    i -= 1;
+                    let feedback = ((value & top_bit_mask) != 0) != ((unreflected_byte >> i) & 1 == 1);
+                    value = (value << 1) & mask;
+                    if feedback {
+                        value ^= poly;
+                    }
+                }
+                if reflected {
+                    value = value.reverse_bits() >> (BITS - width);
+                }
+                table[ix] = value;
+                ix += 1;
+            }
+            table
+        }
+    };
+}
+impl_const_table!(u8, const_table_u8);
+impl_const_table!(u16, const_table_u16);
+impl_const_table!(u32, const_table_u32);
+impl_const_table!(u64, const_table_u64);
+impl_const_table!(u128, const_table_u128);
+impl_const_table!(usize, const_table_usize);
+
 #[cfg(test)]
 mod tests {
     macro_rules! prep_and_common {
-        ($t:ty, $module:ident, $poly:expr, $reflected:expr, [$($el:expr)*]) => {
+        ($t:ty, $module:ident, $poly:expr, $width:expr, $reflected:expr, [$($el:expr)*]) => {
             #[allow(overflowing_literals)]
             mod $module {
                 use super::super::{Table, fill_table};
@@ -36,7 +111,7 @@ mod tests {
                 lazy_static! {
                     static ref TABLE: Table<$t> = {
                         let mut table: Table<$t> = [0; 256];
-                        fill_table(&mut table, $poly, $reflected);
+                        fill_table(&mut table, $poly, $width, $reflected);
                         table
                     };
                 }
@@ -56,8 +131,59 @@ mod tests {
     // The literal tables were taken from
     // http://www.sunshine2k.de/coding/javascript/crc/crc_js.html
 
+    mod layout {
+        use super::super::{Table, fill_table};
+        use super::super::super::reflect;
+
+        fn table(poly: u32, width: usize, reflected: bool) -> Table<u32> {
+            let mut table = [0; 256];
+            fill_table(&mut table, poly, width, reflected);
+            table
+        }
+
+        #[test]
+        fn entry_0_is_0() {
+            assert_eq!(table(0x04C11DB7, 32, false)[0], 0);
+            assert_eq!(table(0x04C11DB7, 32, true)[0], 0);
+            assert_eq!(table(0x864CFB, 24, false)[0], 0);
+        }
+
+        #[test]
+        fn non_reflected_entry_1_is_poly() {
+            assert_eq!(table(0x04C11DB7, 32, false)[0x01], 0x04C11DB7);
+            assert_eq!(table(0x864CFB, 24, false)[0x01], 0x864CFB);
+        }
+
+        #[test]
+        fn reflected_entry_80_is_reflected_poly() {
+            assert_eq!(table(0x04C11DB7, 32, true)[0x80], 0xEDB88320);
+            assert_eq!(table(0x864CFB, 24, true)[0x80], reflect(0x864CFB, 24));
+        }
+
+        #[test]
+        fn reflected_is_reflection_of_non_reflected() {
+            let non_reflected = table(0x864CFB, 24, false);
+            let reflected = table(0x864CFB, 24, true);
+            for (ix, &value) in non_reflected.iter().enumerate() {
+                let reflected_ix = (ix as u8).reverse_bits() as usize;
+                assert_eq!(reflected[reflected_ix], reflect(value, 24), "ix: {}", ix);
+            }
+        }
+
+        #[test]
+        fn published_crc_32_entries() {
+            // From the table used by zlib and many other CRC-32 implementations
+            let table = table(0x04C11DB7, 32, true);
+            assert_eq!(table[0x01], 0x77073096);
+            assert_eq!(table[0x02], 0xEE0E612C);
+            assert_eq!(table[0x03], 0x990951BA);
+            assert_eq!(table[0x80], 0xEDB88320);
+            assert_eq!(table[0xFF], 0x2D02EF8D);
+        }
+    }
+
     prep_and_common!(
-        u8, crc_8_maxim, 0x31, true,
+        u8, crc_8_maxim, 0x31, 8, true,
         [
             0x00 0x5E 0xBC 0xE2 0x61 0x3F 0xDD 0x83 0xC2 0x9C 0x7E 0x20 0xA3 0xFD 0x1F 0x41
             0x9D 0xC3 0x21 0x7F 0xFC 0xA2 0x40 0x1E 0x5F 0x01 0xE3 0xBD 0x3E 0x60 0x82 0xDC
@@ -79,7 +205,7 @@ mod tests {
     );
 
     prep_and_common!(
-        u16, crc_16_arc, 0x8005, true,
+        u16, crc_16_arc, 0x8005, 16, true,
         [
             0x0000 0xC0C1 0xC181 0x0140 0xC301 0x03C0 0x0280 0xC241
             0xC601 0x06C0 0x0780 0xC741 0x0500 0xC5C1 0xC481 0x0440
@@ -117,7 +243,7 @@ mod tests {
     );
 
     prep_and_common!(
-        u32, crc_32_posix, 0x4C11DB7, false,
+        u32, crc_32_posix, 0x4C11DB7, 32, false,
         [
             0x00000000 0x04C11DB7 0x09823B6E 0x0D4326D9 0x130476DC 0x17C56B6B 0x1A864DB2 0x1E475005
             0x2608EDB8 0x22C9F00F 0x2F8AD6D6 0x2B4BCB61 0x350C9B64 0x31CD86D3 0x3C8EA00A 0x384FBDBD
@@ -153,4 +279,41 @@ mod tests {
             0xAFB010B1 0xAB710D06 0xA6322BDF 0xA2F33668 0xBCB4666D 0xB8757BDA 0xB5365D03 0xB1F740B4
         ]
     );
+
+    mod const_tables {
+        use super::super::*;
+        use primitive::ValueType;
+
+        static CRC32: Table<u32> = const_table_u32(0x04C11DB7, 32, true);
+
+        fn runtime<T: ValueType>(poly: T, width: usize, reflected: bool) -> Table<T> {
+            let mut table = [T::from(0); 256];
+            fill_table(&mut table, poly, width, reflected);
+            table
+        }
+
+        #[test]
+        fn static_crc32() {
+            assert_eq!(&CRC32[..], &runtime(0x04C11DB7u32, 32, true)[..]);
+        }
+
+        #[test]
+        fn match_fill_table() {
+            for &reflected in &[false, true] {
+                for width in 1..=8 {
+                    let poly = 0x07u8 & (0xFF >> (8 - width)) | 1;
+                    assert_eq!(const_table_u8(poly, width, reflected), runtime(poly, width, reflected));
+                }
+                for &width in &[10, 12, 15, 16] {
+                    assert_eq!(&const_table_u16(0x233, width, reflected)[..], &runtime(0x233u16, width, reflected)[..]);
+                }
+                for &width in &[24, 31, 32] {
+                    assert_eq!(&const_table_u32(0x5D6DCB, width, reflected)[..], &runtime(0x5D6DCBu32, width, reflected)[..]);
+                }
+                assert_eq!(&const_table_u64(0x42F0E1EBA9EA3693, 64, reflected)[..], &runtime(0x42F0E1EBA9EA3693u64, 64, reflected)[..]);
+                assert_eq!(&const_table_u128(0x0308C0111011401440411, 82, reflected)[..], &runtime(0x0308C0111011401440411u128, 82, reflected)[..]);
+                assert_eq!(&const_table_usize(0x04C11DB7, 32, reflected)[..], &runtime(0x04C11DB7usize, 32, reflected)[..]);
+            }
+        }
+    }
 }
@@ -0,0 +1,31 @@
+//! Helpers shared by the tests of the implementations.
+
+use std::vec::Vec;
+
+
+/// A xorshift generator, to keep the test data reproducible without extra dependencies.
+pub(crate) struct Random(pub(crate) u64);
+
+impl Random {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub(crate) fn wide(&mut self) -> u128 {
+        (self.next() as u128) << 64 | self.next() as u128
+    }
+
+    /// Returns up to `max_len` bytes; the length is random too.
+    pub(crate) fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.next() as usize % (max_len + 1);
+        self.data(len)
+    }
+
+    /// Returns exactly `len` bytes.
+    pub(crate) fn data(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
@@ -0,0 +1,202 @@
+use super::ValueType;
+use core::ops::{Not, Shl, Shr, BitAnd, BitXor};
+
+
+/// A `ValueType` backed by a byte array, for algorithms wider than the widest
+/// primitive integer, or for targets where `u128` arithmetic is slow.
+///
+/// The bytes are stored in big-endian order: `0[0]` is the most significant byte.
+/// The default width is the full size of the array, `N * 8` bits.
+/// Since shift amounts are `u8`, `N` must be between 1 and 32; other sizes fail to compile:
+///
+/// ```compile_fail
+/// use crc_rocksoft::primitive::Wide;
+///
+/// let value = Wide::<33>::from(1);
+/// ```
+///
+/// ```
+/// use crc_rocksoft::*;
+/// use crc_rocksoft::primitive::*;
+///
+/// // CRC-82/DARC without u128
+/// let spec = CrcTable::new_with_width(
+///     82,
+///     Wide([0x00, 0x30, 0x8C, 0x01, 0x11, 0x01, 0x14, 0x01, 0x44, 0x04, 0x11]),
+///     Wide([0; 11]),
+///     true, true,
+///     Wide([0; 11]),
+/// );
+/// let mut hasher = CrcTableHasher::from(spec);
+/// hasher.update_from_slice(b"123456789");
+/// assert_eq!(hasher.finish(), Wide([0x00, 0x9E, 0xA8, 0x3F, 0x62, 0x50, 0x23, 0x80, 0x1F, 0xD6, 0x12]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Wide<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Wide<N> {
+    /// Fails to evaluate, and so to compile, for an unsupported `N`;
+    /// it's referenced by the operations that every use of a `ValueType` goes through.
+    const VALID_SIZE: () = assert!(N >= 1 && N <= 32, "the size of Wide must be between 1 and 32 bytes");
+}
+
+impl<const N: usize> From<u8> for Wide<N> {
+    fn from(value: u8) -> Self {
+        let () = Self::VALID_SIZE;
+        let mut bytes = [0; N];
+        bytes[N - 1] = value;
+        Wide(bytes)
+    }
+}
+
+impl<const N: usize> Not for Wide<N> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        let mut bytes = self.0;
+        for b in bytes.iter_mut() {
+            *b = !*b;
+        }
+        Wide(bytes)
+    }
+}
+
+impl<const N: usize> BitXor for Wide<N> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        let mut bytes = self.0;
+        for (b, r) in bytes.iter_mut().zip(rhs.0.iter()) {
+            *b ^= *r;
+        }
+        Wide(bytes)
+    }
+}
+
+impl<const N: usize> BitAnd for Wide<N> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        let mut bytes = self.0;
+        for (b, r) in bytes.iter_mut().zip(rhs.0.iter()) {
+            *b &= *r;
+        }
+        Wide(bytes)
+    }
+}
+
+/// Shifting by `N * 8` bits or more yields zero.
+impl<const N: usize> Shl<u8> for Wide<N> {
+    type Output = Self;
+
+    fn shl(self, rhs: u8) -> Self {
+        let byte_shift = rhs as usize / 8;
+        let bit_shift = rhs % 8;
+        let mut bytes = [0; N];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            let src = i + byte_shift;
+            if src < N {
+                *b = self.0[src] << bit_shift;
+                if bit_shift != 0 && src + 1 < N {
+                    *b |= self.0[src + 1] >> (8 - bit_shift);
+                }
+            }
+        }
+        Wide(bytes)
+    }
+}
+
+/// Shifting by `N * 8` bits or more yields zero.
+impl<const N: usize> Shr<u8> for Wide<N> {
+    type Output = Self;
+
+    fn shr(self, rhs: u8) -> Self {
+        let byte_shift = rhs as usize / 8;
+        let bit_shift = rhs % 8;
+        let mut bytes = [0; N];
+        for (i, b) in bytes.iter_mut().enumerate().skip(byte_shift) {
+            let src = i - byte_shift;
+            *b = self.0[src] >> bit_shift;
+            if bit_shift != 0 && src > 0 {
+                *b |= self.0[src - 1] << (8 - bit_shift);
+            }
+        }
+        Wide(bytes)
+    }
+}
+
+impl<const N: usize> ValueType for Wide<N> {
+    fn to_u8(self) -> u8 {
+        let () = Self::VALID_SIZE;
+        self.0[N - 1]
+    }
+
+    fn reverse_bits(self) -> Self {
+        let mut bytes = self.0;
+        bytes.reverse();
+        for b in bytes.iter_mut() {
+            *b = b.reverse_bits();
+        }
+        Wide(bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Wide;
+    use super::super::ValueType;
+
+    fn from_u128(value: u128) -> Wide<16> {
+        Wide(value.to_be_bytes())
+    }
+
+    #[test]
+    fn from_u8() {
+        assert_eq!(Wide::<3>::from(0xAB), Wide([0, 0, 0xAB]));
+        assert_eq!(Wide::<3>([0x12, 0x34, 0x56]).to_u8(), 0x56);
+    }
+
+    #[test]
+    fn shl_across_bytes() {
+        assert_eq!(Wide([0x00, 0x81, 0xFF]) << 1, Wide([0x01, 0x03, 0xFE]));
+        assert_eq!(Wide([0x00, 0x81, 0xFF]) << 8, Wide([0x81, 0xFF, 0x00]));
+        assert_eq!(Wide([0x00, 0x81, 0xFF]) << 12, Wide([0x1F, 0xF0, 0x00]));
+        assert_eq!(Wide([0x00, 0x81, 0xFF]) << 0, Wide([0x00, 0x81, 0xFF]));
+        assert_eq!(Wide([0xFF, 0xFF, 0xFF]) << 24, Wide([0, 0, 0]));
+    }
+
+    #[test]
+    fn shr_across_bytes() {
+        assert_eq!(Wide([0x81, 0xFF, 0x00]) >> 1, Wide([0x40, 0xFF, 0x80]));
+        assert_eq!(Wide([0x81, 0xFF, 0x00]) >> 8, Wide([0x00, 0x81, 0xFF]));
+        assert_eq!(Wide([0x81, 0xFF, 0x00]) >> 12, Wide([0x00, 0x08, 0x1F]));
+        assert_eq!(Wide([0x81, 0xFF, 0x00]) >> 0, Wide([0x81, 0xFF, 0x00]));
+        assert_eq!(Wide([0xFF, 0xFF, 0xFF]) >> 24, Wide([0, 0, 0]));
+    }
+
+    #[test]
+    fn matches_u128() {
+        let value = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        let other = 0xF0F0_0F0F_1234_5678_9ABC_DEF0_5555_AAAAu128;
+        for shift in 0..128 {
+            assert_eq!(from_u128(value) << shift, from_u128(value << shift), "<< {}", shift);
+            assert_eq!(from_u128(value) >> shift, from_u128(value >> shift), ">> {}", shift);
+        }
+        assert_eq!(!from_u128(value), from_u128(!value));
+        assert_eq!(from_u128(value) ^ from_u128(other), from_u128(value ^ other));
+        assert_eq!(from_u128(value) & from_u128(other), from_u128(value & other));
+    }
+
+    #[test]
+    fn reflection() {
+        assert_eq!(Wide([0x01, 0x00, 0xF0]).reverse_bits(), Wide([0x0F, 0x00, 0x80]));
+        let value = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        assert_eq!(from_u128(value).reverse_bits(), from_u128(ValueType::reverse_bits(value)));
+    }
+
+    #[test]
+    fn default_width() {
+        assert_eq!(Wide::<11>::default_width(), 88);
+    }
+}
@@ -0,0 +1,177 @@
+//! Conformance checks for third-party implementations of `CrcHasher` and `CrcBitInput`.
+//!
+//! This module is only available with the `testkit` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use crc_rocksoft::primitive::*;
+//! use crc_rocksoft::testkit::assert_hasher_conformance;
+//!
+//! let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+//! assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0xCBF43926)]);
+//! ```
+
+use ::{CrcSpec, CrcHasher, CrcBitInput};
+use primitive::ValueType;
+use primitive::reference::reference_crc;
+
+
+/// The message used for computing the standard check value of a CRC algorithm.
+pub const CHECK_INPUT: &[u8] = b"123456789";
+
+fn bytewise<T, H: CrcHasher<T>>(hasher: &mut H, data: &[u8]) {
+    for &b in data {
+        hasher.update(b);
+    }
+}
+
+/// Checks that hashers created by `make` follow the contract of `CrcHasher`
+/// and compute checksums according to `spec`, panicking otherwise.
+///
+/// The following properties are checked:
+///
+/// * a new hasher computes the same checksums as the bitwise
+///   [reference implementation](../primitive/reference/index.html) of `spec`,
+///   for the empty message and for the standard check input `"123456789"`;
+/// * `finish` doesn't modify the state of the hasher, so it can be called repeatedly,
+///   including in the middle of a message;
+/// * `reset` makes the hasher behave as if it was newly created;
+/// * `update_from_slice` is equivalent to calling `update` for each byte;
+/// * each of the `vectors` (pairs of a message and its expected checksum) is reproduced.
+pub fn assert_hasher_conformance<T, H, F>(make: F, spec: &impl CrcSpec<T>, vectors: &[(&[u8], T)])
+    where T: ValueType, H: CrcHasher<T>, F: Fn() -> H
+{
+    let empty = reference_crc(spec, &[]);
+    let check = reference_crc(spec, CHECK_INPUT);
+
+    let mut hasher = make();
+    assert_eq!(hasher.finish(), empty, "the checksum of an empty message differs from the reference");
+    bytewise(&mut hasher, CHECK_INPUT);
+    assert_eq!(hasher.finish(), check, "the check value differs from the reference");
+    assert_eq!(hasher.finish(), check, "finish is not idempotent");
+
+    hasher.reset();
+    assert_eq!(hasher.finish(), empty, "reset doesn't restore the initial state");
+    hasher.update_from_slice(CHECK_INPUT);
+    assert_eq!(hasher.finish(), check, "update_from_slice differs from update after reset");
+
+    let mut hasher = make();
+    let (head, tail) = CHECK_INPUT.split_at(4);
+    hasher.update_from_slice(head);
+    assert_eq!(hasher.finish(), reference_crc(spec, head), "the checksum of a prefix differs from the reference");
+    hasher.update_from_slice(tail);
+    assert_eq!(hasher.finish(), check, "finish in the middle of a message changes the state");
+
+    for &(data, expected) in vectors {
+        let mut hasher = make();
+        bytewise(&mut hasher, data);
+        assert_eq!(hasher.finish(), expected, "update: wrong checksum of {:?}", data);
+
+        let mut hasher = make();
+        hasher.update_from_slice(data);
+        assert_eq!(hasher.finish(), expected, "update_from_slice: wrong checksum of {:?}", data);
+    }
+}
+
+/// Checks everything [`assert_hasher_conformance`](fn.assert_hasher_conformance.html) does,
+/// and also that:
+///
+/// * `update_bit` for each bit of a byte, in the order defined by `refin`, is equivalent to `update`;
+/// * `update_last_bits` with 8 bits is equivalent to `update`;
+/// * `refin` agrees with `spec`.
+pub fn assert_bit_input_conformance<T, H, F>(make: F, spec: &impl CrcSpec<T>, vectors: &[(&[u8], T)])
+    where T: ValueType, H: CrcBitInput<T>, F: Fn() -> H
+{
+    assert_hasher_conformance(&make, spec, vectors);
+    let check = reference_crc(spec, CHECK_INPUT);
+
+    let mut hasher = make();
+    for &byte in CHECK_INPUT {
+        for i in 0..8 {
+            hasher.update_bit((byte >> (if spec.refin() { i } else { 7 - i })) & 1 == 1);
+        }
+    }
+    assert_eq!(hasher.finish(), check, "update_bit differs from update");
+
+    let mut hasher = make();
+    for &byte in CHECK_INPUT {
+        hasher.update_last_bits(byte, 8);
+    }
+    assert_eq!(hasher.finish(), check, "update_last_bits differs from update");
+    assert_eq!(hasher.refin(), spec.refin(), "refin differs from the spec");
+}
+
+#[cfg(test)]
+mod tests {
+    use primitive::{CrcTable, CrcTableHasher};
+    use super::{assert_hasher_conformance, assert_bit_input_conformance};
+
+    #[test]
+    fn crc_table_hasher_u8() {
+        let spec = CrcTable::new(0x31u8, 0u8, true, true, 0u8);
+        assert_bit_input_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0xA1)]);
+    }
+
+    #[test]
+    fn crc_table_hasher_u16() {
+        let spec = CrcTable::new(0x1021u16, 0xFFFFu16, false, false, 0u16);
+        assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0x29B1), (b"", 0xFFFF)]);
+    }
+
+    #[test]
+    fn crc_table_hasher_u32() {
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0xCBF43926), (b"", 0)]);
+    }
+
+    #[test]
+    fn crc_table_hasher_u64() {
+        let spec = CrcTable::new(0x42F0E1EBA9EA3693u64, 0u64, false, false, 0u64);
+        assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0x6C40DF5F0B497347)]);
+    }
+
+    #[test]
+    fn crc_table_hasher_u128() {
+        let spec = CrcTable::new_with_width(82, 0x0308C0111011401440411u128, 0u128, true, true, 0u128);
+        assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0x09EA83F625023801FD612)]);
+    }
+
+    #[test]
+    fn crc_table_hasher_sub_width() {
+        let spec = CrcTable::new_with_width(24, 0x864CFBu32, 0xB704CEu32, false, false, 0u32);
+        assert_hasher_conformance(|| CrcTableHasher::from(&spec), &spec, &[(b"123456789", 0x21CF02)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "finish is not idempotent")]
+    fn detects_mutating_finish() {
+        use ::CrcHasher;
+        use core::cell::Cell;
+
+        /// A hasher that forgets the message when finished.
+        struct Forgetful<'a> {
+            spec: &'a CrcTable<u32>,
+            value: Cell<u32>
+        }
+
+        impl<'a> CrcHasher<u32> for Forgetful<'a> {
+            fn reset(&mut self) {
+                self.value.set(self.spec.start());
+            }
+
+            fn update(&mut self, byte: u8) {
+                self.value.set(self.spec.update(self.value.get(), byte));
+            }
+
+            fn finish(&self) -> u32 {
+                let result = self.spec.finish(self.value.get());
+                self.value.set(self.spec.start());
+                result
+            }
+        }
+
+        let spec = CrcTable::new(0x04C11DB7u32, 0xFFFFFFFFu32, true, true, 0xFFFFFFFFu32);
+        assert_hasher_conformance(|| Forgetful { spec: &spec, value: Cell::new(spec.start()) }, &spec, &[]);
+    }
+}